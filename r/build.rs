@@ -0,0 +1,176 @@
+// Генерация IDL программы из атрибутов shank (feature "idl")
+//
+// cargo build --features idl записывает idl/r.json. Адрес программы берется
+// из переменной окружения PROGRAM_ID (как в тестах), без нее поле metadata.address пустое.
+//
+// Генерация ALL_MERKLE_ROOTS (feature "merkle-roots")
+//
+// cargo build --features merkle-roots строит корни раундов по b/rounds/N/d3.json
+// (каталог можно переопределить переменной ROUNDS_DIR) и записывает их в
+// $OUT_DIR/merkle_roots.rs. Если корень раунда расходится с src/merkle_roots.rs
+// или изменилось количество раундов, сборка завершается ошибкой.
+
+fn main() {
+    #[cfg(feature = "idl")]
+    generate_idl();
+    #[cfg(feature = "merkle-roots")]
+    generate_merkle_roots();
+}
+
+#[cfg(feature = "idl")]
+fn generate_idl() {
+    use std::{env, fs, path::Path};
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-env-changed=PROGRAM_ID");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let lib_rs = Path::new(&manifest_dir).join("src").join("lib.rs");
+
+    let opts = shank_idl::ParseIdlOpts {
+        require_program_address: false,
+        program_address_override: env::var("PROGRAM_ID").ok(),
+        ..Default::default()
+    };
+    let idl = shank_idl::extract_idl(lib_rs.to_str().unwrap(), opts)
+        .expect("Failed to parse program sources")
+        .expect("No IDL could be extracted");
+    let json = idl.try_into_json().expect("Failed to serialize IDL");
+
+    let idl_dir = Path::new(&manifest_dir).join("idl");
+    fs::create_dir_all(&idl_dir).unwrap();
+    fs::write(idl_dir.join("r.json"), json + "\n").unwrap();
+}
+
+// Дерево строится как в r::merkle и крейте r-merkle: листья сортируются,
+// пары хешируются sha256(min || max), непарный узел поднимается без изменений
+#[cfg(feature = "merkle-roots")]
+fn merkle_root(mut leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    leaves.sort();
+    while leaves.len() > 1 {
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let (min, max) = if left <= right { (left, right) } else { (right, left) };
+                    let mut hasher = Sha256::new();
+                    hasher.update(min);
+                    hasher.update(max);
+                    hasher.finalize().into()
+                }
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    leaves.first().copied().unwrap_or_default()
+}
+
+// Корень раунда из d3.json: лист - sha256(wallet || NFTnumber LE), как в инструкции 45
+#[cfg(feature = "merkle-roots")]
+fn round_root(d3_path: &std::path::Path) -> ([u8; 32], usize) {
+    use sha2::{Digest, Sha256};
+
+    let data = std::fs::read_to_string(d3_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", d3_path.display(), err));
+    let entries: serde_json::Value = serde_json::from_str(&data)
+        .unwrap_or_else(|err| panic!("Failed to parse {}: {}", d3_path.display(), err));
+    let entries = entries
+        .as_array()
+        .unwrap_or_else(|| panic!("{} must contain an array", d3_path.display()));
+
+    let leaves: Vec<[u8; 32]> = entries
+        .iter()
+        .map(|entry| {
+            let player = entry["player"]
+                .as_str()
+                .unwrap_or_else(|| panic!("{}: entry without player", d3_path.display()));
+            let nft_number = entry["NFTnumber"]
+                .as_u64()
+                .and_then(|value| u16::try_from(value).ok())
+                .unwrap_or_else(|| panic!("{}: invalid NFTnumber for {}", d3_path.display(), player));
+            let wallet = bs58::decode(player)
+                .into_vec()
+                .ok()
+                .filter(|bytes| bytes.len() == 32)
+                .unwrap_or_else(|| panic!("{}: invalid player address {}", d3_path.display(), player));
+
+            let mut hasher = Sha256::new();
+            hasher.update(&wallet);
+            hasher.update(nft_number.to_le_bytes());
+            hasher.finalize().into()
+        })
+        .collect();
+
+    (merkle_root(leaves), entries.len())
+}
+
+#[cfg(feature = "merkle-roots")]
+fn generate_merkle_roots() {
+    use std::{env, fs, path::{Path, PathBuf}};
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let committed_path = Path::new(&manifest_dir).join("src").join("merkle_roots.rs");
+    let rounds_dir = env::var("ROUNDS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| Path::new(&manifest_dir).join("..").join("b").join("rounds"));
+
+    println!("cargo:rerun-if-env-changed=ROUNDS_DIR");
+    println!("cargo:rerun-if-changed={}", committed_path.display());
+    println!("cargo:rerun-if-changed={}", rounds_dir.display());
+
+    // Зафиксированные корни: строки-литералы массивов в src/merkle_roots.rs
+    let committed: Vec<String> = fs::read_to_string(&committed_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", committed_path.display(), err))
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("[0x"))
+        .map(|line| line.trim_end_matches(',').to_string())
+        .collect();
+
+    // Раунды 1..N идут подряд, пока есть d3.json
+    let mut rounds = Vec::new();
+    loop {
+        let d3_path = rounds_dir.join((rounds.len() + 1).to_string()).join("d3.json");
+        if !d3_path.exists() {
+            break;
+        }
+        println!("cargo:rerun-if-changed={}", d3_path.display());
+        rounds.push(round_root(&d3_path));
+    }
+
+    if rounds.len() != committed.len() {
+        panic!(
+            "Found {} rounds in {}, but src/merkle_roots.rs has {} roots",
+            rounds.len(),
+            rounds_dir.display(),
+            committed.len()
+        );
+    }
+
+    let mut generated = String::from(
+        "// Сгенерировано build.rs (feature \"merkle-roots\") из b/rounds/N/d3.json\n\n",
+    );
+    generated += &format!("pub const ALL_MERKLE_ROOTS: [[u8; 32]; {}] = [\n", rounds.len());
+    for (i, ((root, leaves), committed_root)) in rounds.iter().zip(&committed).enumerate() {
+        let literal = format!(
+            "[{}]",
+            root.iter().map(|byte| format!("0x{:02x}", byte)).collect::<Vec<_>>().join(", ")
+        );
+        if &literal != committed_root {
+            panic!(
+                "Merkle root of round {} does not match src/merkle_roots.rs\nComputed:  {}\nCommitted: {}",
+                i + 1,
+                literal,
+                committed_root
+            );
+        }
+        generated += &format!("    // Раунд {}: {} листьев\n    {},\n", i + 1, leaves, literal);
+    }
+    generated += "];\n";
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("merkle_roots.rs"), generated).unwrap();
+}
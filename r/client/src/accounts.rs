@@ -0,0 +1,274 @@
+//! Декодеры аккаунтов программы
+
+use borsh::BorshDeserialize;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+pub use r::state::{
+    AllowanceRecord, ClaimBitmap, Key, MintRecordV1, MintRecordV2, ProgramConfig, RoundConfig,
+    SplitConfig, SplitRecipient,
+};
+
+/// Запись о минте в одном из форматов: старом (32 байта) или V2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MintRecord {
+    V1(MintRecordV1),
+    V2(MintRecordV2),
+}
+
+impl MintRecord {
+    /// Адрес минта NFT (есть в обоих форматах)
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            MintRecord::V1(record) => record.mint,
+            MintRecord::V2(record) => record.mint,
+        }
+    }
+}
+
+/// Декодирует запись о минте кошелька в раунде; формат определяется по размеру данных
+pub fn decode_mint_record(data: &[u8]) -> Result<MintRecord, ProgramError> {
+    match data.len() {
+        MintRecordV1::LEN => MintRecordV1::try_from_slice(data)
+            .map(MintRecord::V1)
+            .map_err(|_| ProgramError::InvalidAccountData),
+        MintRecordV2::LEN => {
+            let record = MintRecordV2::try_from_slice(data)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            if record.key != Key::MintRecordV2 {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            Ok(MintRecord::V2(record))
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Декодирует конфигурацию раунда
+pub fn decode_round_config(data: &[u8]) -> Result<RoundConfig, ProgramError> {
+    let config = RoundConfig::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if config.key != Key::RoundConfig {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(config)
+}
+
+/// Декодирует конфигурацию программы
+pub fn decode_program_config(data: &[u8]) -> Result<ProgramConfig, ProgramError> {
+    let config = ProgramConfig::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if config.key != Key::ProgramConfig {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(config)
+}
+
+/// Декодирует заголовок битовой карты минтов раунда
+pub fn decode_claim_bitmap(data: &[u8]) -> Result<ClaimBitmap, ProgramError> {
+    if data.len() < ClaimBitmap::HEADER_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let header = ClaimBitmap::try_from_slice(&data[..ClaimBitmap::HEADER_LEN])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if header.key != Key::ClaimBitmap {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(header)
+}
+
+/// Декодирует распределение выручки
+pub fn decode_split_config(data: &[u8]) -> Result<SplitConfig, ProgramError> {
+    let config = SplitConfig::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if config.key != Key::SplitConfig {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(config)
+}
+
+/// Декодирует счетчик минтов кошелька в пределах лимита (инструкция 62)
+pub fn decode_allowance_record(data: &[u8]) -> Result<AllowanceRecord, ProgramError> {
+    let record = AllowanceRecord::try_from_slice(data)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if record.key != Key::AllowanceRecord {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(record)
+}
+
+/// Проверяет по данным битовой карты, заминчен ли NFTnumber
+pub fn is_nft_number_claimed(data: &[u8], nft_number: u16) -> Result<bool, ProgramError> {
+    let header = decode_claim_bitmap(data)?;
+    if u32::from(nft_number) >= header.capacity {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let index = nft_number as usize;
+    let byte = data
+        .get(ClaimBitmap::HEADER_LEN + index / 8)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(byte & (1 << (index % 8)) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r::state::MerkleLeafVersion;
+
+    // Данные аккаунта: сериализованная структура, дополненная нулями до размера аккаунта
+    fn account_data<T: borsh::BorshSerialize>(value: &T, len: usize) -> Vec<u8> {
+        let mut data = borsh::to_vec(value).unwrap();
+        assert!(data.len() <= len);
+        data.resize(len, 0);
+        data
+    }
+
+    fn key(seed: u8) -> Pubkey {
+        Pubkey::new_from_array([seed; 32])
+    }
+
+    fn round_config() -> RoundConfig {
+        RoundConfig {
+            key: Key::RoundConfig,
+            round: 2,
+            merkle_root: [7; 32],
+            collection_mint: key(3),
+            name_template: "Yapster #{nft_number}".to_string(),
+            symbol: "YAP".to_string(),
+            uri_base: "https://a.b".to_string(),
+            seller_fee_basis_points: 500,
+            bump: 254,
+            start_timestamp: 1,
+            end_timestamp: 2,
+            paused: 0,
+            price_lamports: 3,
+            price_mint: key(4),
+            price_amount: 5,
+            burn_basis_points: 6,
+            token_program: key(5),
+            reveal_root: [8; 32],
+            leaf_version: MerkleLeafVersion::V2,
+        }
+    }
+
+    #[test]
+    fn decodes_mint_records_by_length() {
+        let v1 = MintRecordV1 { mint: key(1) };
+        let decoded = decode_mint_record(&account_data(&v1, MintRecordV1::LEN)).unwrap();
+        assert_eq!(decoded, MintRecord::V1(v1));
+        assert_eq!(decoded.mint(), key(1));
+
+        let v2 = MintRecordV2 {
+            key: Key::MintRecordV2,
+            version: MintRecordV2::VERSION,
+            round: 2,
+            mint: key(2),
+            nft_number: 7,
+            minter: key(3),
+            slot: 10,
+            unix_timestamp: 11,
+            bump: 255,
+        };
+        let decoded = decode_mint_record(&account_data(&v2, MintRecordV2::LEN)).unwrap();
+        assert_eq!(decoded.mint(), key(2));
+        assert_eq!(decoded, MintRecord::V2(v2.clone()));
+
+        let wrong_key = MintRecordV2 { key: Key::AllowanceRecord, ..v2 };
+        assert_eq!(
+            decode_mint_record(&account_data(&wrong_key, MintRecordV2::LEN)),
+            Err(ProgramError::UninitializedAccount)
+        );
+        assert_eq!(decode_mint_record(&[0; 33]), Err(ProgramError::InvalidAccountData));
+        assert_eq!(decode_mint_record(&[]), Err(ProgramError::InvalidAccountData));
+    }
+
+    #[test]
+    fn decodes_round_and_program_configs() {
+        let config = round_config();
+        assert_eq!(decode_round_config(&account_data(&config, RoundConfig::LEN)).unwrap(), config);
+        let wrong_key = RoundConfig { key: Key::ProgramConfig, ..round_config() };
+        assert_eq!(
+            decode_round_config(&account_data(&wrong_key, RoundConfig::LEN)),
+            Err(ProgramError::UninitializedAccount)
+        );
+        assert_eq!(decode_round_config(&[1; 8]), Err(ProgramError::InvalidAccountData));
+
+        let config = ProgramConfig {
+            key: Key::ProgramConfig,
+            admin: key(1),
+            pending_admin: Some(key(2)),
+            bump: 253,
+            paused: 1,
+            collection_mint: key(3),
+        };
+        assert_eq!(decode_program_config(&account_data(&config, ProgramConfig::LEN)).unwrap(), config);
+        let wrong_key = ProgramConfig { key: Key::RoundConfig, ..config };
+        assert_eq!(
+            decode_program_config(&account_data(&wrong_key, ProgramConfig::LEN)),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn decodes_claim_bitmap_and_reads_bits() {
+        let header = ClaimBitmap { key: Key::ClaimBitmap, round: 2, capacity: 12, bump: 250 };
+        let mut data = account_data(&header, ClaimBitmap::len(12));
+        assert_eq!(data.len(), ClaimBitmap::HEADER_LEN + 2);
+        data[ClaimBitmap::HEADER_LEN] = 0b0000_0010;
+        data[ClaimBitmap::HEADER_LEN + 1] = 0b0000_1000;
+
+        assert_eq!(decode_claim_bitmap(&data).unwrap(), header);
+        assert!(!is_nft_number_claimed(&data, 0).unwrap());
+        assert!(is_nft_number_claimed(&data, 1).unwrap());
+        assert!(is_nft_number_claimed(&data, 11).unwrap());
+        assert!(!is_nft_number_claimed(&data, 10).unwrap());
+        assert_eq!(is_nft_number_claimed(&data, 12), Err(ProgramError::InvalidArgument));
+
+        assert_eq!(
+            decode_claim_bitmap(&data[..ClaimBitmap::HEADER_LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        // Биты обрезаны: capacity больше размера данных
+        assert_eq!(
+            is_nft_number_claimed(&data[..ClaimBitmap::HEADER_LEN + 1], 11),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let wrong_key = ClaimBitmap { key: Key::SplitConfig, ..header };
+        assert_eq!(
+            decode_claim_bitmap(&account_data(&wrong_key, ClaimBitmap::len(12))),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn decodes_split_config_and_allowance_record() {
+        let config = SplitConfig {
+            key: Key::SplitConfig,
+            bump: 252,
+            total_distributed: 1_000,
+            dust: 3,
+            recipients: vec![
+                SplitRecipient { address: key(1), basis_points: 7000 },
+                SplitRecipient { address: key(2), basis_points: 3000 },
+            ],
+        };
+        assert_eq!(decode_split_config(&account_data(&config, SplitConfig::LEN)).unwrap(), config);
+        let wrong_key = SplitConfig { key: Key::ClaimBitmap, ..config };
+        assert_eq!(
+            decode_split_config(&account_data(&wrong_key, SplitConfig::LEN)),
+            Err(ProgramError::UninitializedAccount)
+        );
+
+        let record = AllowanceRecord { key: Key::AllowanceRecord, round: 2, wallet: key(1), minted: 3, bump: 251 };
+        let data = account_data(&record, AllowanceRecord::LEN);
+        assert_eq!(decode_allowance_record(&data).unwrap(), record);
+        assert_eq!(
+            decode_allowance_record(&data[..AllowanceRecord::LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let wrong_key = AllowanceRecord { key: Key::MintRecordV2, ..record };
+        assert_eq!(
+            decode_allowance_record(&account_data(&wrong_key, AllowanceRecord::LEN)),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Расшифровка кодов ошибок программы (`ProgramError::Custom`)
+
+use num_traits::FromPrimitive;
+use solana_program::program_error::ProgramError;
+
+use r::error::RError;
+
+/// Возвращает ошибку программы по коду `Custom(code)`
+pub fn decode_error_code(code: u32) -> Option<RError> {
+    RError::from_u32(code)
+}
+
+/// Возвращает ошибку программы, если `ProgramError` - это `Custom` с известным кодом
+pub fn decode_program_error(error: &ProgramError) -> Option<RError> {
+    match error {
+        ProgramError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
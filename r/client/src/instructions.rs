@@ -0,0 +1,979 @@
+//! Сборщики инструкций программы
+//!
+//! Порядок аккаунтов совпадает с порядком `next_account_info` в обработчиках
+//! `r/src/instructions/instruction_NN.rs`.
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+
+use r::instruction::{
+    BatchUpdateMetadataArgsV1, CreateClaimBitmapArgsV1, CreateCollectionArgsV1, DeleteMintRecordArgsV1, InitializeProgramConfigArgsV1,
+    ManualMintRecordArgs, MigrateMintRecordArgsV1, MintWithAllowanceArgsV1, MintWithMultiproofArgsV1,
+    MintWithProofArgsV1, PauseArgsV1, ProposeAdminArgsV1, RInstruction, RevealArgsV1, RoundConfigArgs,
+    SetSplitConfigArgsV1, UpdateMetadataArgs, WithdrawArgsV1,
+};
+
+use crate::pda::{
+    find_allowance_record, find_associated_token_account, find_claim_bitmap, find_master_edition,
+    find_metadata, find_mint_authority, find_mint_record, find_program_config, find_program_data,
+    find_round_config, find_split_config, find_token_record, find_treasury,
+};
+
+/// Инструкция 14: ручное создание записи о минте для кошелька (только администратор)
+pub fn manual_mint_record(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    wallet: &Pubkey,
+    args: ManualMintRecordArgs,
+) -> Instruction {
+    let (mint_record, _) = find_mint_record(program_id, args.round(), wallet);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(mint_record, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new_readonly(*wallet, false),
+        ],
+        data: RInstruction::ManualMintRecord(args).pack(),
+    }
+}
+
+/// Инструкция 15: удаление записи о минте кошелька (только администратор)
+pub fn delete_mint_record(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    wallet: &Pubkey,
+    args: DeleteMintRecordArgsV1,
+) -> Instruction {
+    let (mint_record, _) = find_mint_record(program_id, args.round, wallet);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(mint_record, false),
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new_readonly(*wallet, false),
+        ],
+        data: RInstruction::DeleteMintRecord(args).pack(),
+    }
+}
+
+/// Инструкция 39: создание NFT-коллекции с чеканкой на АТА программы (только администратор)
+///
+/// `mint` - новый keypair минта коллекции, должен подписать транзакцию.
+/// `token_program` - SPL Token или Token-2022.
+pub fn create_collection(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    args: CreateCollectionArgsV1,
+) -> Instruction {
+    let (metadata, _) = find_metadata(mint);
+    let (master_edition, _) = find_master_edition(mint);
+    let (program_authority, _) = find_mint_authority(program_id);
+    let token_account = find_associated_token_account(&program_authority, mint, token_program);
+    let (token_record, _) = find_token_record(mint, &token_account);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new(*mint, true),
+            AccountMeta::new_readonly(program_authority, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(token_record, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(program_config, false),
+        ],
+        data: RInstruction::CreateCollection(args).pack(),
+    }
+}
+
+/// Инструкция 42: вывод SOL или токенов с PDA программы (только администратор)
+///
+/// `source` - PDA `["mint_authority"]` или казна `["treasury"]`. Без `token` выводятся
+/// лампорты (на PDA остается минимум для ренты), с `token` - токены с ассоциированного
+/// счета PDA на ассоциированный счет `recipient`. `amount = 0` - все доступное.
+pub fn withdraw(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    source: &Pubkey,
+    recipient: &Pubkey,
+    token: Option<&TokenPayment>,
+    args: WithdrawArgsV1,
+) -> Instruction {
+    let (program_config, _) = find_program_config(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new(*admin, true),
+    ];
+    if let Some(token) = token {
+        accounts.extend([
+            AccountMeta::new_readonly(token.mint, false),
+            AccountMeta::new(find_associated_token_account(source, &token.mint, &token.token_program), false),
+            AccountMeta::new(find_associated_token_account(recipient, &token.mint, &token.token_program), false),
+            AccountMeta::new_readonly(token.token_program, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ]);
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RInstruction::Withdraw(args).pack(),
+    }
+}
+
+/// Инструкция 43: обновление метаданных NFT (только администратор)
+pub fn update_metadata(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    args: UpdateMetadataArgs,
+) -> Instruction {
+    let mint = args.mint();
+    let (metadata, _) = find_metadata(&mint);
+    let (program_authority, _) = find_mint_authority(program_id);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(program_authority, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(program_config, false),
+        ],
+        data: RInstruction::UpdateMetadata(args).pack(),
+    }
+}
+
+/// Оплата минта токеном: минт токена и его программа (SPL Token или Token-2022)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenPayment {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// Инструкция 45: минт pNFT по Merkle proof с добавлением в коллекцию
+///
+/// `payer` - кошелек из списка раунда, он же владелец токена.
+/// `mint` - новый keypair минта, должен подписать транзакцию.
+/// `token_program` - программа токенов раунда (`RoundConfig::token_program_id`).
+/// `payment` - обязателен, если у раунда задана цена в токене (`price_amount > 0`).
+pub fn mint_with_proof(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    collection_mint: &Pubkey,
+    token_program: &Pubkey,
+    payment: Option<&TokenPayment>,
+    args: MintWithProofArgsV1,
+) -> Instruction {
+    let (metadata, _) = find_metadata(mint);
+    let (master_edition, _) = find_master_edition(mint);
+    let (program_authority, _) = find_mint_authority(program_id);
+    let token_account = find_associated_token_account(payer, mint, token_program);
+    let (token_record, _) = find_token_record(mint, &token_account);
+    let (mint_record, _) = find_mint_record(program_id, args.round, payer);
+    let (collection_metadata, _) = find_metadata(collection_mint);
+    let (collection_master_edition, _) = find_master_edition(collection_mint);
+    let (round_config, _) = find_round_config(program_id, args.round);
+    let (claim_bitmap, _) = find_claim_bitmap(program_id, args.round);
+    let (program_config, _) = find_program_config(program_id);
+    let (treasury, _) = find_treasury(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new(*mint, true),
+        AccountMeta::new(program_authority, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(*payer, true), // token_owner
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(token_record, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(mint_record, false),
+        AccountMeta::new_readonly(*collection_mint, false),
+        AccountMeta::new(collection_metadata, false),
+        AccountMeta::new_readonly(collection_master_edition, false),
+        AccountMeta::new_readonly(Pubkey::default(), false), // collection_authority_record (не используется)
+        AccountMeta::new_readonly(round_config, false),
+        AccountMeta::new(claim_bitmap, false), // передается всегда, даже если карта не создана
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new(treasury, false),
+    ];
+    if let Some(payment) = payment {
+        accounts.extend(payment_accounts(payer, &treasury, payment));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RInstruction::MintWithProof(args).pack(),
+    }
+}
+
+// Аккаунты оплаты токеном: минт, счет плательщика, хранилище казны, программа токена
+fn payment_accounts(payer: &Pubkey, treasury: &Pubkey, payment: &TokenPayment) -> [AccountMeta; 4] {
+    [
+        AccountMeta::new(payment.mint, false), // supply меняется при сжигании
+        AccountMeta::new(
+            find_associated_token_account(payer, &payment.mint, &payment.token_program),
+            false,
+        ),
+        AccountMeta::new(
+            find_associated_token_account(treasury, &payment.mint, &payment.token_program),
+            false,
+        ),
+        AccountMeta::new_readonly(payment.token_program, false),
+    ]
+}
+
+/// Инструкция 47: создание конфигурации раунда (только администратор)
+pub fn create_round_config(program_id: &Pubkey, admin: &Pubkey, args: RoundConfigArgs) -> Instruction {
+    let (round_config, _) = find_round_config(program_id, args.round());
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(round_config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_config, false),
+        ],
+        data: RInstruction::CreateRoundConfig(args).pack(),
+    }
+}
+
+/// Инструкция 48: обновление конфигурации раунда (только администратор). Данные V1
+/// меняют только исходные поля, остальные остаются как в текущей конфигурации
+pub fn update_round_config(program_id: &Pubkey, admin: &Pubkey, args: RoundConfigArgs) -> Instruction {
+    let (round_config, _) = find_round_config(program_id, args.round());
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(round_config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: RInstruction::UpdateRoundConfig(args).pack(),
+    }
+}
+
+/// Инструкция 49: инициализация конфигурации программы (подписывает upgrade authority)
+pub fn initialize_program_config(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    args: InitializeProgramConfigArgsV1,
+) -> Instruction {
+    let (program_config, _) = find_program_config(program_id);
+    let (program_data, _) = find_program_data(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(program_config, false),
+            AccountMeta::new(*upgrade_authority, true),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: RInstruction::InitializeProgramConfig(args).pack(),
+    }
+}
+
+/// Инструкция 50: предложение нового администратора
+pub fn propose_admin(program_id: &Pubkey, admin: &Pubkey, args: ProposeAdminArgsV1) -> Instruction {
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(program_config, false),
+            AccountMeta::new_readonly(*admin, true),
+        ],
+        data: RInstruction::ProposeAdmin(args).pack(),
+    }
+}
+
+/// Инструкция 51: принятие прав администратора
+pub fn accept_admin(program_id: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(program_config, false),
+            AccountMeta::new_readonly(*new_admin, true),
+        ],
+        data: RInstruction::AcceptAdmin.pack(),
+    }
+}
+
+/// Инструкция 52: перевод старой записи о минте в формат V2 (только администратор)
+pub fn migrate_mint_record(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    wallet: &Pubkey,
+    args: MigrateMintRecordArgsV1,
+) -> Instruction {
+    let (mint_record, _) = find_mint_record(program_id, args.round, wallet);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(mint_record, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new_readonly(*wallet, false),
+        ],
+        data: RInstruction::MigrateMintRecord(args).pack(),
+    }
+}
+
+/// Инструкция 53: создание битовой карты минтов раунда (только администратор)
+pub fn create_claim_bitmap(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    args: CreateClaimBitmapArgsV1,
+) -> Instruction {
+    let (claim_bitmap, _) = find_claim_bitmap(program_id, args.round);
+    let (program_config, _) = find_program_config(program_id);
+    let (round_config, _) = find_round_config(program_id, args.round);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(claim_bitmap, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new_readonly(round_config, false),
+        ],
+        data: RInstruction::CreateClaimBitmap(args).pack(),
+    }
+}
+
+/// Инструкция 54: постановка на паузу (глобально или для раунда `args.round`)
+pub fn pause(program_id: &Pubkey, admin: &Pubkey, args: PauseArgsV1) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: pause_accounts(program_id, admin, args.round),
+        data: RInstruction::Pause(args).pack(),
+    }
+}
+
+/// Инструкция 55: снятие паузы (глобально или для раунда `args.round`)
+pub fn unpause(program_id: &Pubkey, admin: &Pubkey, args: PauseArgsV1) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: pause_accounts(program_id, admin, args.round),
+        data: RInstruction::Unpause(args).pack(),
+    }
+}
+
+fn pause_accounts(program_id: &Pubkey, admin: &Pubkey, round: Option<u64>) -> Vec<AccountMeta> {
+    let (program_config, _) = find_program_config(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(program_config, false),
+        AccountMeta::new_readonly(*admin, true),
+    ];
+    if let Some(round) = round {
+        let (round_config, _) = find_round_config(program_id, round);
+        accounts.push(AccountMeta::new(round_config, false));
+    }
+    accounts
+}
+
+/// Инструкция 57: создание или замена распределения выручки (только администратор)
+pub fn set_split_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    args: SetSplitConfigArgsV1,
+) -> Instruction {
+    let (split_config, _) = find_split_config(program_id);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(split_config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_config, false),
+        ],
+        data: RInstruction::SetSplitConfig(args).pack(),
+    }
+}
+
+/// Инструкция 58: распределение баланса `source` (казна или `mint_authority`) между
+/// получателями; `recipients` - адреса в порядке SplitConfig. Подпись не требуется
+pub fn distribute(program_id: &Pubkey, source: &Pubkey, recipients: &[Pubkey]) -> Instruction {
+    let (split_config, _) = find_split_config(program_id);
+    let (program_config, _) = find_program_config(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(split_config, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(recipients.iter().map(|recipient| AccountMeta::new(*recipient, false)));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RInstruction::Distribute.pack(),
+    }
+}
+
+/// Инструкция 59: пакетное обновление метаданных (только администратор). Результат
+/// по каждому обновлению - `Vec<BatchUpdateStatus>` (Borsh) в return data
+pub fn batch_update_metadata(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    args: BatchUpdateMetadataArgsV1,
+) -> Instruction {
+    let (program_authority, _) = find_mint_authority(program_id);
+    let (program_config, _) = find_program_config(program_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(program_authority, false),
+        AccountMeta::new(*admin, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+        AccountMeta::new_readonly(program_config, false),
+    ];
+    for update in &args.updates {
+        let (metadata, _) = find_metadata(&update.mint);
+        accounts.push(AccountMeta::new(metadata, false));
+        accounts.push(AccountMeta::new_readonly(update.mint, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RInstruction::BatchUpdateMetadata(args).pack(),
+    }
+}
+
+/// Инструкция 60: раскрытие URI NFT по манифесту раунда; `payer` - любой кошелек.
+/// Лист манифеста - `r::merkle::reveal_leaf`
+pub fn reveal(program_id: &Pubkey, payer: &Pubkey, mint: &Pubkey, args: RevealArgsV1) -> Instruction {
+    let (metadata, _) = find_metadata(mint);
+    let (program_authority, _) = find_mint_authority(program_id);
+    let (round_config, _) = find_round_config(program_id, args.round);
+    let (program_config, _) = find_program_config(program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(program_authority, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(round_config, false),
+            AccountMeta::new_readonly(program_config, false),
+        ],
+        data: RInstruction::Reveal(args).pack(),
+    }
+}
+
+/// Инструкция 61: минт нескольких pNFT по мультидоказательству Merkle
+///
+/// `mints` - новые keypair минтов в порядке `args.nft_numbers`, все подписывают транзакцию.
+/// Остальные параметры - как у [`mint_with_proof`]. Для раунда нужна битовая карта.
+pub fn mint_with_multiproof(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mints: &[Pubkey],
+    collection_mint: &Pubkey,
+    token_program: &Pubkey,
+    payment: Option<&TokenPayment>,
+    args: MintWithMultiproofArgsV1,
+) -> Instruction {
+    let (claim_bitmap, _) = find_claim_bitmap(program_id, args.round);
+    Instruction {
+        program_id: *program_id,
+        accounts: multi_mint_accounts(
+            program_id,
+            payer,
+            mints,
+            collection_mint,
+            token_program,
+            args.round,
+            claim_bitmap,
+            payment,
+        ),
+        data: RInstruction::MintWithMultiproof(args).pack(),
+    }
+}
+
+/// Инструкция 62: минт следующих `args.quantity` pNFT в пределах лимита кошелька
+///
+/// `args.nft_numbers` - все номера из листа кошелька (`r::merkle::allowance_leaf`).
+/// `mints` - новые keypair минтов, по одному на NFT. Остальные параметры - как у
+/// [`mint_with_proof`].
+pub fn mint_with_allowance(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mints: &[Pubkey],
+    collection_mint: &Pubkey,
+    token_program: &Pubkey,
+    payment: Option<&TokenPayment>,
+    args: MintWithAllowanceArgsV1,
+) -> Instruction {
+    let (allowance_record, _) = find_allowance_record(program_id, args.round, payer);
+    Instruction {
+        program_id: *program_id,
+        accounts: multi_mint_accounts(
+            program_id,
+            payer,
+            mints,
+            collection_mint,
+            token_program,
+            args.round,
+            allowance_record,
+            payment,
+        ),
+        data: RInstruction::MintWithAllowance(args).pack(),
+    }
+}
+
+// Аккаунты инструкций 61 и 62: отличаются только учетом минтов (битовая карта
+// или запись лимита), за общими аккаунтами идут аккаунты NFT и оплата токеном
+#[allow(clippy::too_many_arguments)]
+fn multi_mint_accounts(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mints: &[Pubkey],
+    collection_mint: &Pubkey,
+    token_program: &Pubkey,
+    round: u64,
+    claim_account: Pubkey,
+    payment: Option<&TokenPayment>,
+) -> Vec<AccountMeta> {
+    let (program_authority, _) = find_mint_authority(program_id);
+    let (collection_metadata, _) = find_metadata(collection_mint);
+    let (collection_master_edition, _) = find_master_edition(collection_mint);
+    let (round_config, _) = find_round_config(program_id, round);
+    let (program_config, _) = find_program_config(program_id);
+    let (treasury, _) = find_treasury(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(program_authority, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(*collection_mint, false),
+        AccountMeta::new(collection_metadata, false),
+        AccountMeta::new_readonly(collection_master_edition, false),
+        AccountMeta::new_readonly(round_config, false),
+        AccountMeta::new(claim_account, false),
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new(treasury, false),
+    ];
+    for mint in mints {
+        let (metadata, _) = find_metadata(mint);
+        let (master_edition, _) = find_master_edition(mint);
+        let token_account = find_associated_token_account(payer, mint, token_program);
+        let (token_record, _) = find_token_record(mint, &token_account);
+        accounts.extend([
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new(*mint, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(token_record, false),
+        ]);
+    }
+    if let Some(payment) = payment {
+        accounts.extend(payment_accounts(payer, &treasury, payment));
+    }
+    accounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r::instruction::{
+        CreatorArgsV1, ManualMintRecordArgsV1, ManualMintRecordArgsV2, RoundConfigArgsV1, RoundConfigArgsV2,
+        UpdateMetadataArgsV1, UpdateMetadataArgsV2,
+    };
+    use r::state::{MerkleLeafVersion, SplitRecipient};
+    use serde_json::Value;
+
+    // IDL собирается shank из атрибутов #[account] в r/src/instruction.rs
+    const IDL: &str = include_str!("../../idl/r.json");
+
+    struct IdlAccount {
+        name: String,
+        writable: bool,
+        signer: bool,
+        optional: bool,
+    }
+
+    fn idl_accounts(discriminant: u8) -> Vec<IdlAccount> {
+        let idl: Value = serde_json::from_str(IDL).unwrap();
+        let instruction = idl["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|instruction| instruction["discriminant"]["value"] == discriminant)
+            .unwrap_or_else(|| panic!("instruction {} is not in the IDL", discriminant));
+        instruction["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|account| IdlAccount {
+                name: account["name"].as_str().unwrap().to_string(),
+                writable: account["isMut"].as_bool().unwrap(),
+                signer: account["isSigner"].as_bool().unwrap(),
+                optional: account["isOptional"].as_bool().unwrap_or(false),
+            })
+            .collect()
+    }
+
+    // Сравнивает аккаунты инструкции с IDL. Аккаунт IDL с именем repeated стоит на
+    // месте repeated_count аккаунтов (по группе на NFT или получателя); необязательные
+    // аккаунты в конце передаются все или ни одного
+    fn assert_accounts(instruction: &Instruction, repeated: Option<(&str, usize)>) {
+        let idl = idl_accounts(instruction.data[0]);
+        let (head, tail, repeated_count) = match repeated {
+            Some((name, count)) => {
+                let position = idl.iter().position(|account| account.name == name).unwrap();
+                (&idl[..position], &idl[position + 1..], count)
+            }
+            None => (&idl[..], &[][..], 0),
+        };
+        let required = head.iter().take_while(|account| !account.optional).count();
+        let (head_required, head_optional) = head.split_at(required);
+
+        let accounts = &instruction.accounts;
+        let check = |expected: &[IdlAccount], actual: &[AccountMeta]| {
+            for (account, meta) in expected.iter().zip(actual) {
+                assert_eq!(meta.is_signer, account.signer, "{}: signer flag of {}", instruction.data[0], account.name);
+                assert_eq!(meta.is_writable, account.writable, "{}: writable flag of {}", instruction.data[0], account.name);
+            }
+        };
+
+        assert!(accounts.len() >= required, "{}: missing required accounts", instruction.data[0]);
+        check(head_required, &accounts[..required]);
+        let rest = &accounts[required..];
+        if repeated.is_some() {
+            assert!(head_optional.is_empty());
+            assert!(rest.len() >= repeated_count, "{}: missing repeated accounts", instruction.data[0]);
+            let optional = &rest[repeated_count..];
+            assert!(optional.is_empty() || optional.len() == tail.len(), "{}: partial optional accounts", instruction.data[0]);
+            assert!(tail.iter().all(|account| account.optional));
+            check(tail, optional);
+        } else {
+            assert!(rest.is_empty() || rest.len() == head_optional.len(), "{}: partial optional accounts", instruction.data[0]);
+            check(head_optional, rest);
+        }
+    }
+
+    fn assert_round_trip(instruction: &Instruction, expected: RInstruction) {
+        assert_eq!(RInstruction::unpack(&instruction.data).unwrap(), expected);
+        assert_eq!(instruction.data, expected.pack());
+    }
+
+    fn key(seed: u8) -> Pubkey {
+        Pubkey::new_from_array([seed; 32])
+    }
+
+    fn round_config_args_v2() -> RoundConfigArgsV2 {
+        RoundConfigArgsV2 {
+            round: 2,
+            merkle_root: [7; 32],
+            collection_mint: key(3),
+            name_template: "Yapster #{nft_number} R{round}".to_string(),
+            symbol: "YAP".to_string(),
+            uri_base: "https://a.b".to_string(),
+            seller_fee_basis_points: 500,
+            start_timestamp: 1,
+            end_timestamp: 2,
+            price_lamports: 3,
+            price_mint: key(4),
+            price_amount: 5,
+            burn_basis_points: 6,
+            token_program: key(5),
+            reveal_root: [8; 32],
+            leaf_version: MerkleLeafVersion::V2,
+        }
+    }
+
+    fn round_config_args_v1() -> RoundConfigArgsV1 {
+        RoundConfigArgsV1 {
+            round: 2,
+            merkle_root: [7; 32],
+            collection_mint: key(3),
+            name_template: "Yapster #{nft_number}".to_string(),
+            symbol: "YAP".to_string(),
+            uri_base: "https://a.b".to_string(),
+            seller_fee_basis_points: 500,
+        }
+    }
+
+    fn payment() -> TokenPayment {
+        TokenPayment { mint: key(20), token_program: spl_token::id() }
+    }
+
+    #[test]
+    fn admin_instructions_round_trip_and_match_idl() {
+        let program_id = key(1);
+        let admin = key(2);
+        let wallet = key(9);
+
+        for args in [
+            ManualMintRecordArgs::V1(ManualMintRecordArgsV1 { round: 2, mint: key(10) }),
+            ManualMintRecordArgs::V2(ManualMintRecordArgsV2 { round: 2, mint: key(10), nft_number: 7 }),
+        ] {
+            let instruction = manual_mint_record(&program_id, &admin, &wallet, args.clone());
+            assert_round_trip(&instruction, RInstruction::ManualMintRecord(args));
+            assert_accounts(&instruction, None);
+        }
+
+        let args = DeleteMintRecordArgsV1 { round: 2 };
+        let instruction = delete_mint_record(&program_id, &admin, &wallet, args.clone());
+        assert_round_trip(&instruction, RInstruction::DeleteMintRecord(args));
+        assert_accounts(&instruction, None);
+
+        let args = CreateCollectionArgsV1 {
+            name: "Yapster".to_string(),
+            symbol: "YAP".to_string(),
+            uri: "https://a.b/collection.json".to_string(),
+            seller_fee_basis_points: 500,
+            creators: vec![CreatorArgsV1 { address: admin, share: 100 }],
+            collection_size: 0,
+        };
+        let instruction = create_collection(&program_id, &admin, &key(11), &spl_token::id(), args.clone());
+        assert_round_trip(&instruction, RInstruction::CreateCollection(args));
+        assert_accounts(&instruction, None);
+
+        let (treasury, _) = find_treasury(&program_id);
+        for token in [None, Some(payment())] {
+            let args = WithdrawArgsV1 { amount: 0 };
+            let instruction = withdraw(&program_id, &admin, &treasury, &wallet, token.as_ref(), args.clone());
+            assert_round_trip(&instruction, RInstruction::Withdraw(args));
+            assert_accounts(&instruction, None);
+        }
+
+        for args in [
+            UpdateMetadataArgs::V1(UpdateMetadataArgsV1 {
+                mint: key(10),
+                creator: Some(admin),
+                name: None,
+                symbol: None,
+                uri: Some("https://a.b/1.json".to_string()),
+                seller_fee_basis_points: None,
+            }),
+            UpdateMetadataArgs::V2(UpdateMetadataArgsV2 {
+                mint: key(10),
+                creators: None,
+                name: Some("Yapster #1".to_string()),
+                symbol: None,
+                uri: None,
+                seller_fee_basis_points: Some(100),
+                primary_sale_happened: Some(true),
+                is_mutable: None,
+            }),
+        ] {
+            let instruction = update_metadata(&program_id, &admin, args.clone());
+            assert_eq!(instruction.accounts[1].pubkey, key(10));
+            assert_round_trip(&instruction, RInstruction::UpdateMetadata(args));
+            assert_accounts(&instruction, None);
+        }
+
+        for args in [RoundConfigArgs::V1(round_config_args_v1()), RoundConfigArgs::V2(round_config_args_v2())] {
+            let (round_config, _) = find_round_config(&program_id, 2);
+            let instruction = create_round_config(&program_id, &admin, args.clone());
+            assert_eq!(instruction.accounts[0].pubkey, round_config);
+            assert_round_trip(&instruction, RInstruction::CreateRoundConfig(args.clone()));
+            assert_accounts(&instruction, None);
+
+            let instruction = update_round_config(&program_id, &admin, args.clone());
+            assert_round_trip(&instruction, RInstruction::UpdateRoundConfig(args));
+            assert_accounts(&instruction, None);
+        }
+
+        let args = InitializeProgramConfigArgsV1 { admin };
+        let instruction = initialize_program_config(&program_id, &key(12), args.clone());
+        assert_round_trip(&instruction, RInstruction::InitializeProgramConfig(args));
+        assert_accounts(&instruction, None);
+
+        let args = ProposeAdminArgsV1 { new_admin: key(13) };
+        let instruction = propose_admin(&program_id, &admin, args.clone());
+        assert_round_trip(&instruction, RInstruction::ProposeAdmin(args));
+        assert_accounts(&instruction, None);
+
+        let instruction = accept_admin(&program_id, &key(13));
+        assert_round_trip(&instruction, RInstruction::AcceptAdmin);
+        assert_accounts(&instruction, None);
+
+        let args = MigrateMintRecordArgsV1 { round: 2, nft_number: 7 };
+        let instruction = migrate_mint_record(&program_id, &admin, &wallet, args.clone());
+        assert_round_trip(&instruction, RInstruction::MigrateMintRecord(args));
+        assert_accounts(&instruction, None);
+
+        let args = CreateClaimBitmapArgsV1 { round: 2, capacity: 1000 };
+        let instruction = create_claim_bitmap(&program_id, &admin, args.clone());
+        assert_round_trip(&instruction, RInstruction::CreateClaimBitmap(args));
+        assert_accounts(&instruction, None);
+
+        for round in [None, Some(2)] {
+            let args = PauseArgsV1 { scope: 1, round };
+            let instruction = pause(&program_id, &admin, args.clone());
+            assert_round_trip(&instruction, RInstruction::Pause(args.clone()));
+            assert_accounts(&instruction, None);
+
+            let instruction = unpause(&program_id, &admin, args.clone());
+            assert_round_trip(&instruction, RInstruction::Unpause(args));
+            assert_accounts(&instruction, None);
+        }
+
+        let args = SetSplitConfigArgsV1 {
+            recipients: vec![
+                SplitRecipient { address: key(14), basis_points: 6000 },
+                SplitRecipient { address: key(15), basis_points: 4000 },
+            ],
+        };
+        let instruction = set_split_config(&program_id, &admin, args.clone());
+        assert_round_trip(&instruction, RInstruction::SetSplitConfig(args));
+        assert_accounts(&instruction, None);
+
+        let instruction = distribute(&program_id, &treasury, &[key(14), key(15)]);
+        assert_round_trip(&instruction, RInstruction::Distribute);
+        assert_accounts(&instruction, Some(("recipients", 2)));
+
+        let update = |mint: Pubkey| UpdateMetadataArgsV2 {
+            mint,
+            creators: None,
+            name: None,
+            symbol: None,
+            uri: Some("https://a.b/2.json".to_string()),
+            seller_fee_basis_points: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        };
+        let args = BatchUpdateMetadataArgsV1 { updates: vec![update(key(16)), update(key(17))] };
+        let instruction = batch_update_metadata(&program_id, &admin, args.clone());
+        assert_round_trip(&instruction, RInstruction::BatchUpdateMetadata(args));
+        assert_accounts(&instruction, Some(("metadataAccounts", 4)));
+        assert_eq!(instruction.accounts[7].pubkey, key(16));
+        assert_eq!(instruction.accounts[9].pubkey, key(17));
+    }
+
+    #[test]
+    fn mint_instructions_round_trip_and_match_idl() {
+        let program_id = key(1);
+        let payer = key(2);
+        let collection_mint = key(3);
+
+        for token in [None, Some(payment())] {
+            let args = MintWithProofArgsV1 { round: 2, nft_number: 7, proof: vec![[1; 32], [2; 32]] };
+            let instruction = mint_with_proof(
+                &program_id,
+                &payer,
+                &key(10),
+                &collection_mint,
+                &spl_token::id(),
+                token.as_ref(),
+                args.clone(),
+            );
+            assert_round_trip(&instruction, RInstruction::MintWithProof(args));
+            assert_accounts(&instruction, None);
+
+            let mints = [key(10), key(11)];
+            let args = MintWithMultiproofArgsV1 {
+                round: 2,
+                nft_numbers: vec![7, 8],
+                leaf_indices: vec![0, 3],
+                leaf_count: 5,
+                proof: vec![[1; 32]],
+            };
+            let instruction = mint_with_multiproof(
+                &program_id,
+                &payer,
+                &mints,
+                &collection_mint,
+                &spl_token::id(),
+                token.as_ref(),
+                args.clone(),
+            );
+            assert_round_trip(&instruction, RInstruction::MintWithMultiproof(args));
+            assert_accounts(&instruction, Some(("nftAccounts", 10)));
+
+            let args = MintWithAllowanceArgsV1 { round: 2, nft_numbers: vec![7, 8, 9], quantity: 2, proof: vec![] };
+            let instruction = mint_with_allowance(
+                &program_id,
+                &payer,
+                &mints,
+                &collection_mint,
+                &spl_token::id(),
+                token.as_ref(),
+                args.clone(),
+            );
+            assert_round_trip(&instruction, RInstruction::MintWithAllowance(args));
+            assert_accounts(&instruction, Some(("nftAccounts", 10)));
+            let (allowance_record, _) = find_allowance_record(&program_id, 2, &payer);
+            assert_eq!(instruction.accounts[12].pubkey, allowance_record);
+        }
+
+        let args = RevealArgsV1 { round: 2, nft_number: 7, uri: "https://a.b/7.json".to_string(), proof: vec![[1; 32]] };
+        let instruction = reveal(&program_id, &payer, &key(10), args.clone());
+        assert_round_trip(&instruction, RInstruction::Reveal(args));
+        assert_accounts(&instruction, None);
+    }
+
+    #[test]
+    fn versioned_payloads_carry_a_version_byte() {
+        let args = ManualMintRecordArgsV1 { round: 2, mint: key(10) };
+        let data = RInstruction::ManualMintRecord(ManualMintRecordArgs::V1(args)).pack();
+        assert_eq!(&data[..3], &[14, 0, 2]);
+        assert_eq!(data.len(), 1 + 1 + 8 + 32);
+
+        let data = RInstruction::UpdateRoundConfig(RoundConfigArgs::V2(round_config_args_v2())).pack();
+        assert_eq!(&data[..2], &[48, 1]);
+    }
+
+    #[test]
+    fn unpack_rejects_malformed_data() {
+        assert!(RInstruction::unpack(&[]).is_err());
+        // Неизвестный номер инструкции и неизвестная версия данных
+        assert!(RInstruction::unpack(&[63]).is_err());
+        let mut data = RInstruction::ManualMintRecord(ManualMintRecordArgs::V1(ManualMintRecordArgsV1 {
+            round: 2,
+            mint: key(10),
+        }))
+        .pack();
+        data[1] = 2;
+        assert!(RInstruction::unpack(&data).is_err());
+        // Лишние байты
+        let mut data = RInstruction::AcceptAdmin.pack();
+        data.push(0);
+        assert!(RInstruction::unpack(&data).is_err());
+    }
+}
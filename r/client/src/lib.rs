@@ -0,0 +1,20 @@
+//! Клиентская библиотека для программы `r`
+//!
+//! Сборщики инструкций, адреса PDA и декодеры аккаунтов. Типы данных и ошибки
+//! берутся из самой программы (`r` с `no-entrypoint`), поэтому крейт тянет ее
+//! зависимости: `mpl-token-metadata`, `spl-token`, `spl-token-2022` и
+//! `spl-associated-token-account`.
+//!
+//! Сборка под WASM (wasm32-unknown-unknown) для фронтенда `fm` в задачи крейта не
+//! входит: крейт собирается и тестируется только для хоста. `fm` по-прежнему
+//! формирует инструкции в TypeScript, формат данных и аккаунтов - в IDL
+//! (`r/idl/r.json`).
+
+pub mod accounts;
+pub mod errors;
+pub mod instructions;
+pub mod pda;
+
+pub use r::error::RError;
+pub use r::events::{DistributeEvent, REvent, SplitPayout, WithdrawEvent};
+pub use r::instruction::*;
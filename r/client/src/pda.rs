@@ -0,0 +1,79 @@
+//! Адреса PDA программы и связанных программ (Token Metadata, ATA)
+
+use solana_program::{bpf_loader_upgradeable, pubkey::Pubkey};
+
+pub use r::state::{
+    ALLOWANCE_RECORD_SEED, CLAIM_BITMAP_SEED, MINT_AUTHORITY_SEED, MINT_RECORD_SEED,
+    PROGRAM_CONFIG_SEED, ROUND_CONFIG_SEED, SPLIT_CONFIG_SEED, TREASURY_SEED,
+};
+
+/// PDA `mint_authority`
+pub fn find_mint_authority(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], program_id)
+}
+
+/// PDA записи о минте кошелька в раунде
+pub fn find_mint_record(program_id: &Pubkey, round: u64, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MINT_RECORD_SEED, &round.to_le_bytes(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+/// PDA записи лимита минта кошелька в раунде
+pub fn find_allowance_record(program_id: &Pubkey, round: u64, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ALLOWANCE_RECORD_SEED, &round.to_le_bytes(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+/// PDA конфигурации раунда
+pub fn find_round_config(program_id: &Pubkey, round: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROUND_CONFIG_SEED, &round.to_le_bytes()], program_id)
+}
+
+/// PDA битовой карты минтов раунда
+pub fn find_claim_bitmap(program_id: &Pubkey, round: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_BITMAP_SEED, &round.to_le_bytes()], program_id)
+}
+
+/// PDA конфигурации программы
+pub fn find_program_config(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], program_id)
+}
+
+/// PDA казны (оплата минта)
+pub fn find_treasury(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], program_id)
+}
+
+/// PDA распределения выручки
+pub fn find_split_config(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPLIT_CONFIG_SEED], program_id)
+}
+
+/// Аккаунт ProgramData программы (нужен для инициализации конфигурации)
+pub fn find_program_data(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+}
+
+/// Аккаунт метаданных Token Metadata
+pub fn find_metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::accounts::Metadata::find_pda(mint)
+}
+
+/// Аккаунт master edition Token Metadata
+pub fn find_master_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::accounts::MasterEdition::find_pda(mint)
+}
+
+/// Token record pNFT для токен-аккаунта владельца
+pub fn find_token_record(mint: &Pubkey, token_account: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::accounts::TokenRecord::find_pda(mint, token_account)
+}
+
+/// Ассоциированный токен-аккаунт владельца для заданной токен-программы
+pub fn find_associated_token_account(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(owner, mint, token_program)
+}
@@ -0,0 +1,531 @@
+//! Merkle-деревья раундов для программы `r`
+//!
+//! Строит корни и доказательства по `b/rounds/N/d3.json` так, как их проверяет
+//! `r::merkle::verify_merkle_proof`: листья сортируются, пары хешируются
+//! sha256(min || max), непарный узел поднимается на уровень выше без изменений
+//! (так же работает merkletreejs с `sortPairs` в `test_merkle.js`).
+//!
+//! Формат V2 (`MerkleLeafVersion::V2`) строится так же, но листья и узлы берутся
+//! из `r::merkle::wallet_nft_leaf_v2` и `hash_pair_v2` (префиксы 0x00 и 0x01).
+//! Формат Allowance строит один лист `r::merkle::allowance_leaf` на кошелек со
+//! всеми его NFTnumber (`build_allowance_round`).
+
+use std::{collections::BTreeMap, error::Error, fs, path::Path, str::FromStr};
+
+use r::merkle::{
+    allowance_leaf, hash_pair, hash_pair_v2, verify_merkle_multiproof, verify_merkle_proof, verify_merkle_proof_v2,
+    wallet_leaf, wallet_nft_leaf, wallet_nft_leaf_v2,
+};
+use r::state::MerkleLeafVersion;
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Дерево, хранящее все уровни: `layers[0]` - отсортированные листья, последний - корень
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Дерево формата V1
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        Self::with_version(leaves, MerkleLeafVersion::V1)
+    }
+
+    /// Дерево с узлами формата `version`
+    pub fn with_version(mut leaves: Vec<[u8; 32]>, version: MerkleLeafVersion) -> Self {
+        let node: fn(&[u8; 32], &[u8; 32]) -> [u8; 32] = match version {
+            MerkleLeafVersion::V1 => hash_pair,
+            MerkleLeafVersion::V2 | MerkleLeafVersion::Allowance => hash_pair_v2,
+        };
+        leaves.sort();
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// Корень дерева; у пустого дерева - нули
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap().first().copied().unwrap_or_default()
+    }
+
+    /// Доказательство для листа: соседние узлы снизу вверх. Непарный узел
+    /// соседа не имеет, уровень пропускается
+    pub fn proof(&self, leaf: &[u8; 32]) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.layers[0].binary_search(leaf).ok()?;
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < layer.len() {
+                proof.push(layer[sibling]);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+
+    /// Мультидоказательство для нескольких листьев в формате
+    /// `r::merkle::verify_merkle_multiproof`: индексы листьев в порядке `leaves`
+    /// и недостающие соседи снизу вверх, слева направо
+    pub fn multiproof(&self, leaves: &[[u8; 32]]) -> Option<(Vec<u32>, Vec<[u8; 32]>)> {
+        let indices = leaves
+            .iter()
+            .map(|leaf| self.layers[0].binary_search(leaf).ok())
+            .collect::<Option<Vec<usize>>>()?;
+        let mut known = indices.clone();
+        known.sort();
+        known.dedup();
+
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            for (i, index) in known.iter().enumerate() {
+                let sibling = index ^ 1;
+                let sibling_known = known.get(i + 1) == Some(&sibling)
+                    || (i > 0 && known[i - 1] == sibling);
+                if !sibling_known && sibling < layer.len() {
+                    proof.push(layer[sibling]);
+                }
+            }
+            known = known.iter().map(|index| index / 2).collect();
+            known.dedup();
+        }
+        Some((indices.iter().map(|index| *index as u32).collect(), proof))
+    }
+
+    /// Количество листьев
+    pub fn leaf_count(&self) -> u32 {
+        self.layers[0].len() as u32
+    }
+}
+
+/// Запись `d3.json`: кошелек и номер его NFT
+#[derive(Deserialize, Debug, Clone)]
+pub struct RoundEntry {
+    pub player: String,
+    #[serde(rename = "NFTnumber")]
+    pub nft_number: u16,
+}
+
+/// Формат листа: только кошелек (ранние раунды) или кошелек и NFTnumber (инструкция 45).
+/// Листья `Wallet` не проверяет ни одна инструкция программы, они нужны только для
+/// пересчета корней ранних раундов
+/// (V1 или V2 с программой и индексом раунда, как в аргументах инструкций)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafKind {
+    Wallet,
+    WalletNft,
+    WalletNftV2 { program_id: Pubkey, round: u64 },
+}
+
+impl LeafKind {
+    /// Разбирает имя формата; для `wallet-nft-v2` нужны программа и раунд
+    pub fn parse(name: &str, program_id: Option<Pubkey>, round: Option<u64>) -> Result<Self> {
+        match name {
+            "wallet" => Ok(LeafKind::Wallet),
+            "wallet-nft" => Ok(LeafKind::WalletNft),
+            "wallet-nft-v2" => Ok(LeafKind::WalletNftV2 {
+                program_id: program_id.ok_or("wallet-nft-v2 leaves require --program-id")?,
+                round: round.ok_or("wallet-nft-v2 leaves require --round")?,
+            }),
+            _ => Err(format!(
+                "unknown leaf kind '{}', expected wallet, wallet-nft or wallet-nft-v2",
+                name
+            )
+            .into()),
+        }
+    }
+
+    pub fn leaf(&self, wallet: &Pubkey, nft_number: u16) -> [u8; 32] {
+        match self {
+            LeafKind::Wallet => wallet_leaf(wallet),
+            LeafKind::WalletNft => wallet_nft_leaf(wallet, nft_number),
+            LeafKind::WalletNftV2 { program_id, round } => {
+                wallet_nft_leaf_v2(program_id, *round, wallet, nft_number)
+            }
+        }
+    }
+
+    pub fn version(&self) -> MerkleLeafVersion {
+        match self {
+            LeafKind::Wallet | LeafKind::WalletNft => MerkleLeafVersion::V1,
+            LeafKind::WalletNftV2 { .. } => MerkleLeafVersion::V2,
+        }
+    }
+}
+
+/// Доказательство кошелька в JSON: узлы в hex, в порядке проверки
+#[derive(Serialize, Debug, Clone)]
+pub struct WalletProof {
+    pub player: String,
+    #[serde(rename = "NFTnumber")]
+    pub nft_number: u16,
+    pub leaf: String,
+    pub proof: Vec<String>,
+}
+
+/// Мультидоказательство кошелька с несколькими NFTnumber (инструкция 61)
+#[derive(Serialize, Debug, Clone)]
+pub struct WalletMultiproof {
+    pub player: String,
+    #[serde(rename = "NFTnumbers")]
+    pub nft_numbers: Vec<u16>,
+    pub indices: Vec<u32>,
+    #[serde(rename = "leafCount")]
+    pub leaf_count: u32,
+    pub proof: Vec<String>,
+}
+
+/// Корень раунда, доказательства всех кошельков и мультидоказательства
+/// кошельков, встречающихся несколько раз
+pub struct RoundTree {
+    pub root: [u8; 32],
+    pub proofs: Vec<WalletProof>,
+    pub multiproofs: Vec<WalletMultiproof>,
+}
+
+/// Доказательство листа с лимитом кошелька (инструкция 62): все его NFTnumber в порядке d3.json
+#[derive(Serialize, Debug, Clone)]
+pub struct AllowanceProof {
+    pub player: String,
+    #[serde(rename = "NFTnumbers")]
+    pub nft_numbers: Vec<u16>,
+    pub leaf: String,
+    pub proof: Vec<String>,
+}
+
+/// Корень раунда формата Allowance и доказательства всех кошельков
+pub struct AllowanceTree {
+    pub root: [u8; 32],
+    pub proofs: Vec<AllowanceProof>,
+}
+
+pub fn read_round(path: &Path) -> Result<Vec<RoundEntry>> {
+    let data = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok(serde_json::from_str(&data).map_err(|err| format!("{}: {}", path.display(), err))?)
+}
+
+/// Строит дерево раунда и проверяет каждое доказательство on-chain функцией проверки
+pub fn build_round(entries: &[RoundEntry], kind: LeafKind) -> Result<RoundTree> {
+    let leaves = entries
+        .iter()
+        .map(|entry| {
+            let wallet = Pubkey::from_str(&entry.player)
+                .map_err(|err| format!("invalid player address {}: {}", entry.player, err))?;
+            Ok(kind.leaf(&wallet, entry.nft_number))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Одинаковые листья неразличимы в дереве и в мультидоказательстве
+    let mut sorted = leaves.clone();
+    sorted.sort();
+    if let Some(pair) = sorted.windows(2).find(|pair| pair[0] == pair[1]) {
+        let entry = &entries[leaves.iter().position(|leaf| *leaf == pair[0]).unwrap()];
+        return Err(format!("duplicate entry for {} (NFTnumber {})", entry.player, entry.nft_number).into());
+    }
+
+    let version = kind.version();
+    let verify = match version {
+        MerkleLeafVersion::V1 => verify_merkle_proof,
+        MerkleLeafVersion::V2 | MerkleLeafVersion::Allowance => verify_merkle_proof_v2,
+    };
+    let tree = MerkleTree::with_version(leaves.clone(), version);
+    let root = tree.root();
+
+    let mut proofs = Vec::with_capacity(entries.len());
+    for (entry, leaf) in entries.iter().zip(leaves.iter().copied()) {
+        let proof = tree.proof(&leaf).expect("leaf is in the tree");
+        if !verify(leaf, &proof, root) {
+            return Err(format!("proof for {} (NFTnumber {}) does not verify", entry.player, entry.nft_number).into());
+        }
+        proofs.push(WalletProof {
+            player: entry.player.clone(),
+            nft_number: entry.nft_number,
+            leaf: to_hex(&leaf),
+            proof: proof.iter().map(|node| to_hex(node)).collect(),
+        });
+    }
+
+    // Листья каждого кошелька в порядке d3.json
+    let mut wallets: BTreeMap<&str, Vec<(u16, [u8; 32])>> = BTreeMap::new();
+    for (entry, leaf) in entries.iter().zip(&leaves) {
+        wallets.entry(&entry.player).or_default().push((entry.nft_number, *leaf));
+    }
+
+    let mut multiproofs = Vec::new();
+    for (player, items) in wallets.into_iter().filter(|(_, items)| items.len() > 1) {
+        let wallet_leaves: Vec<[u8; 32]> = items.iter().map(|(_, leaf)| *leaf).collect();
+        let (indices, proof) = tree.multiproof(&wallet_leaves).expect("leaves are in the tree");
+        let indexed: Vec<(u32, [u8; 32])> = indices.iter().copied().zip(wallet_leaves).collect();
+        if !verify_merkle_multiproof(version, tree.leaf_count(), &indexed, &proof, root) {
+            return Err(format!("multiproof for {} does not verify", player).into());
+        }
+        multiproofs.push(WalletMultiproof {
+            player: player.to_string(),
+            nft_numbers: items.iter().map(|(nft_number, _)| *nft_number).collect(),
+            indices,
+            leaf_count: tree.leaf_count(),
+            proof: proof.iter().map(|node| to_hex(node)).collect(),
+        });
+    }
+
+    Ok(RoundTree { root, proofs, multiproofs })
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Корень в виде литерала Rust-массива, как в `src/merkle_roots.rs`
+pub fn root_literal(root: &[u8; 32]) -> String {
+    let bytes: Vec<String> = root.iter().map(|byte| format!("0x{:02x}", byte)).collect();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// Строит дерево раунда формата Allowance: записи одного кошелька объединяются
+/// в один лист, лимит - количество записей. Каждое доказательство проверяется
+pub fn build_allowance_round(entries: &[RoundEntry], program_id: &Pubkey, round: u64) -> Result<AllowanceTree> {
+    let mut wallets: BTreeMap<&str, Vec<u16>> = BTreeMap::new();
+    for entry in entries {
+        let nft_numbers = wallets.entry(&entry.player).or_default();
+        if nft_numbers.contains(&entry.nft_number) {
+            return Err(format!("duplicate entry for {} (NFTnumber {})", entry.player, entry.nft_number).into());
+        }
+        nft_numbers.push(entry.nft_number);
+    }
+
+    let leaves = wallets
+        .iter()
+        .map(|(player, nft_numbers)| {
+            let wallet = Pubkey::from_str(player)
+                .map_err(|err| format!("invalid player address {}: {}", player, err))?;
+            allowance_leaf(program_id, round, &wallet, nft_numbers)
+                .ok_or_else(|| format!("{} has more than {} NFT numbers", player, u16::MAX).into())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let tree = MerkleTree::with_version(leaves.clone(), MerkleLeafVersion::Allowance);
+    let root = tree.root();
+
+    let mut proofs = Vec::with_capacity(leaves.len());
+    for ((player, nft_numbers), leaf) in wallets.into_iter().zip(leaves) {
+        let proof = tree.proof(&leaf).expect("leaf is in the tree");
+        if !verify_merkle_proof_v2(leaf, &proof, root) {
+            return Err(format!("proof for {} does not verify", player).into());
+        }
+        proofs.push(AllowanceProof {
+            player: player.to_string(),
+            nft_numbers,
+            leaf: to_hex(&leaf),
+            proof: proof.iter().map(|node| to_hex(node)).collect(),
+        });
+    }
+
+    Ok(AllowanceTree { root, proofs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(count: usize) -> Vec<RoundEntry> {
+        (0..count)
+            .map(|i| RoundEntry { player: Pubkey::new_unique().to_string(), nft_number: i as u16 + 1 })
+            .collect()
+    }
+
+    fn from_hex(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    fn proof_nodes(proof: &WalletProof) -> Vec<[u8; 32]> {
+        proof.proof.iter().map(|node| from_hex(node)).collect()
+    }
+
+    #[test]
+    fn round_proofs_verify_on_chain() {
+        let program_id = Pubkey::new_unique();
+        for count in 1..=9 {
+            let entries = entries(count);
+            for kind in [LeafKind::WalletNft, LeafKind::WalletNftV2 { program_id, round: 4 }] {
+                let tree = build_round(&entries, kind).unwrap();
+                assert_eq!(tree.proofs.len(), count);
+                for proof in &tree.proofs {
+                    let wallet = Pubkey::from_str(&proof.player).unwrap();
+                    assert!(
+                        r::merkle::verify_wallet_nft(
+                            kind.version(),
+                            &program_id,
+                            4,
+                            &wallet,
+                            proof.nft_number,
+                            &proof_nodes(proof),
+                            tree.root,
+                        ),
+                        "{:?}, {} leaves",
+                        kind,
+                        count
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_is_rejected() {
+        let entries = entries(7);
+        let tree = build_round(&entries, LeafKind::WalletNft).unwrap();
+        for proof in &tree.proofs {
+            let mut leaf = from_hex(&proof.leaf);
+            assert!(verify_merkle_proof(leaf, &proof_nodes(proof), tree.root));
+            leaf[31] ^= 1;
+            assert!(!verify_merkle_proof(leaf, &proof_nodes(proof), tree.root));
+
+            let wallet = Pubkey::from_str(&proof.player).unwrap();
+            let other = wallet_nft_leaf(&wallet, proof.nft_number + 100);
+            assert!(!verify_merkle_proof(other, &proof_nodes(proof), tree.root));
+        }
+    }
+
+    #[test]
+    fn single_leaf_is_the_root() {
+        let entries = entries(1);
+        let tree = build_round(&entries, LeafKind::WalletNft).unwrap();
+        assert_eq!(to_hex(&tree.root), tree.proofs[0].leaf);
+        assert!(tree.proofs[0].proof.is_empty());
+    }
+
+    fn leaves(count: usize) -> Vec<[u8; 32]> {
+        (0..count).map(|_| wallet_leaf(&Pubkey::new_unique())).collect()
+    }
+
+    // Пары (индекс листа, лист) и узлы proof
+    type Multiproof = (Vec<(u32, [u8; 32])>, Vec<[u8; 32]>);
+
+    // Мультидоказательство генератора для листьев sorted[i] при i из subset
+    fn multiproof(tree: &MerkleTree, sorted: &[[u8; 32]], subset: &[usize]) -> Multiproof {
+        let chosen: Vec<[u8; 32]> = subset.iter().map(|i| sorted[*i]).collect();
+        let (indices, proof) = tree.multiproof(&chosen).unwrap();
+        (indices.into_iter().zip(chosen).collect(), proof)
+    }
+
+    #[test]
+    fn multiproofs_verify_for_every_subset() {
+        for count in [1, 2, 3, 5, 8] {
+            for version in [MerkleLeafVersion::V1, MerkleLeafVersion::V2] {
+                let tree = MerkleTree::with_version(leaves(count), version);
+                let sorted = tree.layers[0].clone();
+                for mask in 1..1u32 << count {
+                    let subset: Vec<usize> = (0..count).filter(|i| mask & (1 << i) != 0).collect();
+                    let (indexed, proof) = multiproof(&tree, &sorted, &subset);
+                    assert!(
+                        verify_merkle_multiproof(version, tree.leaf_count(), &indexed, &proof, tree.root()),
+                        "{:?}, {} leaves, subset {:?}",
+                        version,
+                        count,
+                        subset
+                    );
+                    // Порядок листьев в аргументах не важен
+                    let reversed: Vec<_> = indexed.iter().rev().copied().collect();
+                    assert!(verify_merkle_multiproof(version, tree.leaf_count(), &reversed, &proof, tree.root()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn multiproof_with_duplicate_indices_is_rejected() {
+        let tree = MerkleTree::new(leaves(5));
+        let sorted = tree.layers[0].clone();
+        let (mut indexed, proof) = multiproof(&tree, &sorted, &[1, 3]);
+        indexed.push(indexed[0]);
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 5, &indexed, &proof, tree.root()));
+
+        // Тот же индекс с другим листом
+        let (mut indexed, proof) = multiproof(&tree, &sorted, &[2]);
+        indexed.push((indexed[0].0, sorted[4]));
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 5, &indexed, &proof, tree.root()));
+    }
+
+    #[test]
+    fn multiproof_with_out_of_range_index_is_rejected() {
+        let tree = MerkleTree::new(leaves(5));
+        let sorted = tree.layers[0].clone();
+        let (indexed, proof) = multiproof(&tree, &sorted, &[4]);
+        // Непарный последний лист поднимается без соседа; за пределами дерева - отказ
+        for index in [5, 6, 7, u32::MAX] {
+            let moved = [(index, indexed[0].1)];
+            assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 5, &moved, &proof, tree.root()));
+        }
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 5, &[], &[], tree.root()));
+    }
+
+    #[test]
+    fn multiproof_with_wrong_leaf_count_is_rejected() {
+        for count in [2, 3, 5, 8] {
+            let tree = MerkleTree::new(leaves(count));
+            let sorted = tree.layers[0].clone();
+            for subset in [vec![0], vec![count - 1], vec![0, count - 1]] {
+                let (indexed, proof) = multiproof(&tree, &sorted, &subset);
+                // Лишний уровень требует узла, которого нет в proof
+                let doubled = count as u32 * 2;
+                assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, doubled, &indexed, &proof, tree.root()));
+                assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 0, &indexed, &proof, tree.root()));
+            }
+            // Последний лист за пределами меньшего дерева
+            let (indexed, proof) = multiproof(&tree, &sorted, &[count - 1]);
+            let fewer = count as u32 - 1;
+            assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, fewer, &indexed, &proof, tree.root()));
+        }
+
+        // Меньшее дерево другой высоты оставляет узлы proof неиспользованными
+        let tree = MerkleTree::new(leaves(5));
+        let sorted = tree.layers[0].clone();
+        let (indexed, proof) = multiproof(&tree, &sorted, &[0]);
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 4, &indexed, &proof, tree.root()));
+    }
+
+    #[test]
+    fn multiproof_with_same_path_leaf_count_verifies() {
+        // Путь листа 0 одинаков в деревьях из 7 и 8 листьев: корень получается тем же,
+        // листья все равно входят в дерево, поэтому такое число листьев допустимо
+        let tree = MerkleTree::new(leaves(8));
+        let sorted = tree.layers[0].clone();
+        let (indexed, proof) = multiproof(&tree, &sorted, &[0]);
+        assert!(verify_merkle_multiproof(MerkleLeafVersion::V1, 7, &indexed, &proof, tree.root()));
+    }
+
+    #[test]
+    fn multiproof_with_extra_or_missing_node_is_rejected() {
+        let tree = MerkleTree::new(leaves(8));
+        let sorted = tree.layers[0].clone();
+        let (indexed, mut proof) = multiproof(&tree, &sorted, &[1, 6]);
+        proof.push(proof[0]);
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 8, &indexed, &proof, tree.root()));
+        proof.truncate(proof.len() - 2);
+        assert!(!verify_merkle_multiproof(MerkleLeafVersion::V1, 8, &indexed, &proof, tree.root()));
+    }
+
+    #[test]
+    fn duplicate_entry_is_rejected() {
+        let mut entries = entries(3);
+        entries.push(entries[1].clone());
+        assert!(build_round(&entries, LeafKind::WalletNft).is_err());
+    }
+}
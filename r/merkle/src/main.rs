@@ -0,0 +1,104 @@
+//! CLI: корень Merkle раунда и доказательства кошельков
+//!
+//! r-merkle <d3.json> [--leaf wallet-nft|wallet|wallet-nft-v2|allowance] [--program-id <id>]
+//!          [--round <index>] [--proofs <out.json>] [--multiproofs <out.json>]
+//!
+//! `wallet` - формат ранних раундов: по таким доказательствам программа не минтит.
+//! Для `wallet-nft-v2` и `allowance` нужны адрес программы и индекс раунда из
+//! аргументов инструкций (для `b/rounds/N` это N - 1). В формате `allowance` лист
+//! один на кошелек, `--proofs` записывает доказательства для инструкции 62.
+//!
+//! Печатает корень в hex и литералом для `src/merkle_roots.rs`. С `--proofs`
+//! записывает доказательства всех кошельков в JSON. Каждое доказательство
+//! проверяется перед выводом, при ошибке код возврата ненулевой. С `--multiproofs`
+//! записывает мультидоказательства кошельков с несколькими NFTnumber (инструкция 61).
+
+use std::{env, fs, path::PathBuf, process, str::FromStr};
+
+use r_merkle::{
+    build_allowance_round, build_round, read_round, root_literal, to_hex, LeafKind, Result,
+};
+use solana_program::pubkey::Pubkey;
+
+const USAGE: &str = "usage: r-merkle <d3.json> [--leaf wallet-nft|wallet|wallet-nft-v2|allowance] \
+[--program-id <id>] [--round <index>] [--proofs <out.json>] [--multiproofs <out.json>]";
+
+fn run() -> Result<()> {
+    let mut input: Option<PathBuf> = None;
+    let mut leaf_name = String::from("wallet-nft");
+    let mut program_id: Option<Pubkey> = None;
+    let mut round: Option<u64> = None;
+    let mut proofs_path: Option<PathBuf> = None;
+    let mut multiproofs_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--leaf" => leaf_name = args.next().ok_or(USAGE)?,
+            "--program-id" => {
+                let value = args.next().ok_or(USAGE)?;
+                program_id = Some(
+                    Pubkey::from_str(&value)
+                        .map_err(|err| format!("invalid program id {}: {}", value, err))?,
+                );
+            }
+            "--round" => round = Some(args.next().ok_or(USAGE)?.parse()?),
+            "--proofs" => proofs_path = Some(args.next().ok_or(USAGE)?.into()),
+            "--multiproofs" => multiproofs_path = Some(args.next().ok_or(USAGE)?.into()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if input.is_none() && !arg.starts_with("--") => input = Some(arg.into()),
+            _ => return Err(format!("unexpected argument '{}'\n{}", arg, USAGE).into()),
+        }
+    }
+    let input = input.ok_or(USAGE)?;
+    let entries = read_round(&input)?;
+
+    if leaf_name == "allowance" {
+        let program_id = program_id.ok_or("allowance leaves require --program-id")?;
+        let round = round.ok_or("allowance leaves require --round")?;
+        let tree = build_allowance_round(&entries, &program_id, round)?;
+
+        println!("Wallets: {} (Allowance)", tree.proofs.len());
+        println!("Root: {}", to_hex(&tree.root));
+        println!("{},", root_literal(&tree.root));
+
+        if let Some(proofs_path) = proofs_path {
+            fs::write(&proofs_path, serde_json::to_string_pretty(&tree.proofs)?)?;
+            println!("Proofs written to {}", proofs_path.display());
+        }
+        println!("All {} proofs verified", tree.proofs.len());
+        return Ok(());
+    }
+
+    let leaf_kind = LeafKind::parse(&leaf_name, program_id, round)?;
+    let round = build_round(&entries, leaf_kind)?;
+
+    println!("Leaves: {} ({:?})", entries.len(), leaf_kind.version());
+    println!("Root: {}", to_hex(&round.root));
+    println!("{},", root_literal(&round.root));
+
+    if let Some(proofs_path) = proofs_path {
+        fs::write(&proofs_path, serde_json::to_string_pretty(&round.proofs)?)?;
+        println!("Proofs written to {}", proofs_path.display());
+    }
+    if let Some(multiproofs_path) = multiproofs_path {
+        fs::write(&multiproofs_path, serde_json::to_string_pretty(&round.multiproofs)?)?;
+        println!("Multiproofs written to {}", multiproofs_path.display());
+    }
+    println!(
+        "All {} proofs and {} multiproofs verified",
+        round.proofs.len(),
+        round.multiproofs.len()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
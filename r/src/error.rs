@@ -0,0 +1,223 @@
+// Ошибки программы (ProgramError::Custom с фиксированными кодами)
+//
+// Коды ошибок стабильны: новые варианты добавляются только в конец,
+// существующие номера не меняются и не переиспользуются.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error, FromPrimitive)]
+pub enum RError {
+    // Merkle proof
+    #[error("Wallet is not on the allowlist for this round")]
+    NotOnAllowlist = 0,
+    #[error("Wallet has already minted in this round")]
+    AlreadyMinted = 1,
+
+    // Адреса аккаунтов
+    #[error("Invalid collection mint")]
+    InvalidCollectionMint = 2,
+    #[error("Invalid collection metadata account")]
+    InvalidCollectionMetadata = 3,
+    #[error("Invalid collection master edition account")]
+    InvalidCollectionMasterEdition = 4,
+    #[error("Invalid program authority PDA")]
+    InvalidProgramAuthority = 5,
+    #[error("Invalid mint record PDA")]
+    InvalidMintRecordAddress = 6,
+    #[error("Invalid token program")]
+    InvalidTokenProgram = 7,
+    #[error("Mint account does not match instruction data")]
+    MintMismatch = 8,
+
+    // Конфигурация раунда
+    #[error("Invalid round config PDA")]
+    InvalidRoundConfigAddress = 10,
+    #[error("Round config is not initialized")]
+    RoundConfigNotInitialized = 11,
+    #[error("Round config already exists")]
+    RoundConfigAlreadyExists = 12,
+    #[error("Name template is too long")]
+    NameTooLong = 13,
+    #[error("Symbol is too long")]
+    SymbolTooLong = 14,
+    #[error("URI is too long")]
+    UriTooLong = 15,
+    #[error("Seller fee basis points exceed 10000")]
+    InvalidSellerFeeBasisPoints = 16,
+
+    // Конфигурация программы и администратор
+    #[error("Invalid program config PDA")]
+    InvalidProgramConfigAddress = 17,
+    #[error("Program config is not initialized")]
+    ProgramConfigNotInitialized = 18,
+    #[error("Program config is already initialized")]
+    ProgramConfigAlreadyInitialized = 19,
+    #[error("Signer is not the program admin")]
+    Unauthorized = 20,
+    #[error("No pending admin to accept")]
+    NoPendingAdmin = 21,
+    #[error("Signer is not the pending admin")]
+    NotPendingAdmin = 22,
+    #[error("Invalid program data account")]
+    InvalidProgramData = 23,
+    #[error("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority = 24,
+
+    // Средства
+    #[error("Insufficient funds in program PDA")]
+    InsufficientPdaFunds = 25,
+
+    // Записи о минте
+    #[error("Mint record is already migrated")]
+    MintRecordAlreadyMigrated = 26,
+
+    // Битовая карта минтов раунда
+    #[error("Invalid claim bitmap PDA")]
+    InvalidClaimBitmapAddress = 27,
+    #[error("Claim bitmap for this round already exists")]
+    ClaimBitmapAlreadyExists = 28,
+    #[error("Claim bitmap capacity must be between 1 and 65536")]
+    InvalidClaimBitmapCapacity = 29,
+    #[error("NFT number is out of claim bitmap range")]
+    NftNumberOutOfRange = 30,
+    #[error("NFT number has already been minted in this round")]
+    NftNumberAlreadyClaimed = 31,
+
+    // Окно минта раунда
+    #[error("Round has not started yet")]
+    RoundNotStarted = 32,
+    #[error("Round has ended")]
+    RoundEnded = 33,
+    #[error("Invalid mint window")]
+    InvalidMintWindow = 34,
+
+    // Пауза
+    #[error("Minting is paused")]
+    MintPaused = 35,
+    #[error("Metadata updates are paused")]
+    UpdatePaused = 36,
+    #[error("Withdrawals are paused")]
+    WithdrawPaused = 37,
+    #[error("Invalid pause scope")]
+    InvalidPauseScope = 38,
+
+    // Казна
+    #[error("Invalid treasury PDA")]
+    InvalidTreasuryAddress = 39,
+    #[error("Nothing to withdraw or distribute: no tokens or no lamports above the rent-exempt minimum")]
+    InvalidWithdrawAmount = 40,
+
+    // Оплата токеном
+    #[error("Invalid payment mint")]
+    InvalidPaymentMint = 41,
+    #[error("Invalid payment vault account")]
+    InvalidPaymentVault = 42,
+    #[error("Burn basis points exceed 10000")]
+    InvalidBurnBasisPoints = 43,
+
+    // Вывод средств
+    #[error("Withdraw source must be the mint authority or treasury PDA")]
+    InvalidWithdrawSource = 44,
+    #[error("Invalid token account")]
+    InvalidTokenAccount = 45,
+
+    // Распределение выручки
+    #[error("Invalid split config account address")]
+    InvalidSplitConfigAddress = 46,
+    #[error("Split config is not initialized")]
+    SplitConfigNotInitialized = 47,
+    #[error("Split must list between 1 and 10 unique recipients")]
+    InvalidSplitRecipients = 48,
+    #[error("Split shares must be positive and sum to 10000 basis points")]
+    InvalidSplitShares = 49,
+    #[error("Recipient account does not match the split config")]
+    SplitRecipientMismatch = 50,
+
+    // Коллекция
+    #[error("Creators must list at most 5 unique addresses with shares summing to 100")]
+    InvalidCreators = 51,
+    #[error("Collection has not been created")]
+    CollectionNotConfigured = 52,
+
+    // Метаданные
+    #[error("Invalid metadata account")]
+    InvalidMetadataAccount = 53,
+    #[error("Metadata is immutable")]
+    MetadataImmutable = 54,
+
+    // Отложенное раскрытие
+    #[error("Reveal is not configured for this round")]
+    RevealNotConfigured = 55,
+    #[error("Reveal commitment can only be set before the round starts and cannot be changed")]
+    RevealRootLocked = 56,
+    #[error("URI is not in the committed reveal manifest")]
+    InvalidRevealProof = 57,
+    #[error("NFT does not belong to this round or NFTnumber")]
+    RevealMintMismatch = 58,
+    #[error("Name template must contain {{nft_number}} and {{round}} for reveal")]
+    InvalidRevealConfig = 59,
+
+    // Формат Merkle-дерева
+    #[error("Instruction does not support the round's Merkle leaf version")]
+    UnsupportedLeafVersion = 60,
+
+    // Минт нескольких NFT по мультидоказательству
+    #[error("NFT numbers and leaf indices must be non-empty and of equal length")]
+    InvalidMultiMintArgs = 61,
+    #[error("Not enough compute units to mint all NFTs")]
+    InsufficientComputeBudget = 62,
+    #[error("Minting several NFTs requires a claim bitmap for the round")]
+    ClaimBitmapRequired = 63,
+
+    // Лимит минта кошелька
+    #[error("Invalid allowance record account address")]
+    InvalidAllowanceRecordAddress = 64,
+    #[error("Mint quantity exceeds the wallet allowance")]
+    AllowanceExceeded = 65,
+    #[error("Mint quantity must be at least 1")]
+    InvalidMintQuantity = 66,
+
+    // Изменения, допустимые только до начала раунда
+    #[error("Round has already started")]
+    RoundAlreadyStarted = 67,
+
+    // Программы для CPI
+    #[error("Invalid Token Metadata program")]
+    InvalidTokenMetadataProgram = 68,
+
+    // Размер листа лимита минта
+    #[error("Wallet allowance exceeds u16::MAX NFT numbers")]
+    AllowanceTooLarge = 69,
+
+    // Получатель NFT
+    #[error("NFT can only be minted to the payer's wallet")]
+    InvalidTokenOwner = 70,
+}
+
+impl From<RError> for ProgramError {
+    fn from(e: RError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for RError {
+    fn type_of() -> &'static str {
+        "RError"
+    }
+}
+
+impl PrintProgramError for RError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
@@ -0,0 +1,63 @@
+// События программы для сверки учета
+//
+// Пишутся в логи транзакции через sol_log_data ("Program data: <base64>"):
+// первый байт - номер события, далее Borsh-структура с данными.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum REvent {
+    // Вывод SOL или токенов с PDA программы (инструкция 42)
+    Withdraw(WithdrawEvent) = 0,
+    // Распределение выручки между получателями (инструкция 58)
+    Distribute(DistributeEvent) = 1,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawEvent {
+    // PDA, с которого выведены средства
+    pub source: Pubkey,
+    pub recipient: Pubkey,
+    pub admin: Pubkey,
+    // None - вывод SOL, Some - минт выведенного токена
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    // Остаток на источнике после вывода (лампорты или токены)
+    pub remaining: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SplitPayout {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributeEvent {
+    pub source: Pubkey,
+    // Сумма выплат; amount + dust - распределяемый баланс источника, dust -
+    // остаток этого распределения (он же в SplitConfig.dust)
+    pub amount: u64,
+    pub dust: u64,
+    pub payouts: Vec<SplitPayout>,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+impl REvent {
+    pub fn emit(&self) -> Result<(), ProgramError> {
+        let data = borsh::to_vec(self)?;
+        sol_log_data(&[&data]);
+        Ok(())
+    }
+
+    // Разбор данных одной записи "Program data"
+    pub fn unpack(data: &[u8]) -> Option<Self> {
+        borsh::from_slice(data).ok()
+    }
+}
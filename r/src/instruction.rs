@@ -0,0 +1,612 @@
+// Инструкции программы и их данные (Borsh)
+//
+// Первый байт данных - номер инструкции (совпадает с номером файла instruction_NN.rs),
+// далее Borsh-структура с данными. Лишние байты и неверный UTF-8 отклоняются.
+//
+// Формат данных выпущенной версии не меняется. Если операции нужны новые поля,
+// ее данные - перечисление версий ...Args { V1, V2, ... }: после номера инструкции
+// идет байт версии, затем структура ...ArgsVN. Новые поля добавляются новой версией,
+// обработчик приводит старые версии к последней (недостающие поля получают значения
+// по умолчанию).
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::{ShankInstruction, ShankType};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::{Key, MerkleLeafVersion, RoundConfig, SplitRecipient};
+
+// Атрибуты #[account] описывают порядок аккаунтов для IDL (shank) и должны
+// совпадать с порядком next_account_info в instruction_NN.rs
+#[derive(BorshSerialize, BorshDeserialize, ShankInstruction, Debug, Clone, PartialEq, Eq)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum RInstruction {
+    // Ручное создание записи о минте (в V1 без NFTnumber - запись с NFTnumber 0)
+    #[account(0, writable, name = "mint_record", desc = "PDA [\"minted\", round, wallet]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the record")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(4, name = "wallet", desc = "Wallet the record is created for")]
+    ManualMintRecord(ManualMintRecordArgs) = 14,
+    // Удаление записи о минте для раунда
+    #[account(0, writable, signer, name = "admin", desc = "Program admin, receives the rent")]
+    #[account(1, writable, name = "mint_record", desc = "PDA [\"minted\", round, wallet]")]
+    #[account(2, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(3, name = "wallet", desc = "Wallet the record belongs to")]
+    DeleteMintRecord(DeleteMintRecordArgsV1) = 15,
+    // Создание NFT-коллекции с чеканкой на АТА программы
+    #[account(0, writable, name = "metadata", desc = "Metadata account of the collection")]
+    #[account(1, writable, name = "master_edition", desc = "Master edition of the collection")]
+    #[account(2, writable, signer, name = "mint", desc = "New collection mint keypair")]
+    #[account(3, name = "program_authority", desc = "PDA [\"mint_authority\"], update authority and token owner")]
+    #[account(4, writable, signer, name = "admin", desc = "Program admin, pays for the accounts")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(7, name = "spl_token_program", desc = "SPL Token or Token-2022 program")]
+    #[account(8, writable, name = "token_account", desc = "Associated token account of the program authority")]
+    #[account(9, writable, name = "token_record", desc = "Token record of the program token account")]
+    #[account(10, name = "associated_token_program", desc = "Associated Token Account program")]
+    #[account(11, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(12, name = "rent", desc = "Rent sysvar")]
+    #[account(13, writable, name = "program_config", desc = "Program config PDA [\"config\"], records the collection mint")]
+    CreateCollection(CreateCollectionArgsV1) = 39,
+    // Вывод SOL или токенов с PDA программы на адрес из списка аккаунтов
+    #[account(0, writable, name = "source", desc = "PDA [\"mint_authority\"] or treasury PDA [\"treasury\"]")]
+    #[account(1, writable, name = "recipient", desc = "Recipient of the lamports or owner of the recipient token account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(4, writable, signer, name = "admin", desc = "Program admin, pays for the recipient token account")]
+    #[account(5, optional, name = "mint", desc = "Token mint, only when withdrawing tokens")]
+    #[account(6, optional, writable, name = "source_token_account", desc = "Associated token account of the source PDA")]
+    #[account(7, optional, writable, name = "recipient_token_account", desc = "Associated token account of the recipient")]
+    #[account(8, optional, name = "token_program", desc = "SPL Token or Token-2022 program")]
+    #[account(9, optional, name = "associated_token_program", desc = "Associated Token Account program")]
+    Withdraw(WithdrawArgsV1) = 42,
+    // Универсальное обновление метаданных NFT (в V1 - один создатель)
+    #[account(0, writable, name = "metadata", desc = "Metadata account of the NFT")]
+    #[account(1, name = "mint", desc = "Mint of the NFT")]
+    #[account(2, name = "program_authority", desc = "PDA [\"mint_authority\"], update authority")]
+    #[account(3, writable, signer, name = "admin", desc = "Program admin")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(6, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(7, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    UpdateMetadata(UpdateMetadataArgs) = 43,
+    // Минт pNFT с проверкой Merkle proof и коллекцией
+    #[account(0, writable, name = "metadata", desc = "Metadata account of the new NFT")]
+    #[account(1, writable, name = "master_edition", desc = "Master edition of the new NFT")]
+    #[account(2, writable, signer, name = "mint", desc = "New mint keypair")]
+    #[account(3, writable, name = "program_authority", desc = "PDA [\"mint_authority\"]")]
+    #[account(4, writable, signer, name = "payer", desc = "Allowlisted wallet, pays for the mint")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(7, name = "spl_token_program", desc = "Token program of the round: SPL Token or Token-2022")]
+    #[account(8, writable, signer, name = "token_owner", desc = "Token owner, same as payer")]
+    #[account(9, writable, name = "token_account", desc = "Associated token account of the owner")]
+    #[account(10, writable, name = "token_record", desc = "pNFT token record")]
+    #[account(11, name = "associated_token_program", desc = "Associated Token Account program")]
+    #[account(12, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(13, name = "rent", desc = "Rent sysvar")]
+    #[account(14, writable, name = "mint_record", desc = "PDA [\"minted\", round, payer]")]
+    #[account(15, name = "collection_mint", desc = "Collection mint")]
+    #[account(16, writable, name = "collection_metadata", desc = "Collection metadata")]
+    #[account(17, name = "collection_master_edition", desc = "Collection master edition")]
+    #[account(18, name = "collection_authority_record", desc = "Unused, any account")]
+    #[account(19, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(20, writable, name = "claim_bitmap", desc = "Claim bitmap PDA [\"claims\", round], may be uninitialized")]
+    #[account(21, name = "program_config", desc = "Program config PDA [\"config\"], global pause flags")]
+    #[account(22, writable, name = "treasury", desc = "Treasury PDA [\"treasury\"], receives the mint price")]
+    #[account(23, optional, writable, name = "payment_mint", desc = "Payment token mint, only when the round has a token price")]
+    #[account(24, optional, writable, name = "payment_token_account", desc = "Payer token account of the payment mint")]
+    #[account(25, optional, writable, name = "payment_vault", desc = "Treasury ATA of the payment mint, created if missing")]
+    #[account(26, optional, name = "payment_token_program", desc = "SPL Token or Token-2022 program of the payment mint")]
+    MintWithProof(MintWithProofArgsV1) = 45,
+    // Создание конфигурации раунда со всеми параметрами минта (в V1 - исходные поля)
+    #[account(0, writable, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    CreateRoundConfig(RoundConfigArgs) = 47,
+    // Обновление конфигурации раунда (V1 меняет только исходные поля)
+    #[account(0, writable, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the realloc")]
+    #[account(2, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(3, name = "system_program", desc = "System program")]
+    UpdateRoundConfig(RoundConfigArgs) = 48,
+    // Инициализация конфигурации программы
+    #[account(0, writable, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(1, writable, signer, name = "upgrade_authority", desc = "Program upgrade authority")]
+    #[account(2, name = "program_data", desc = "ProgramData account of the program")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitializeProgramConfig(InitializeProgramConfigArgsV1) = 49,
+    // Предложение нового администратора
+    #[account(0, writable, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(1, signer, name = "admin", desc = "Current program admin")]
+    ProposeAdmin(ProposeAdminArgsV1) = 50,
+    // Принятие прав администратора
+    #[account(0, writable, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(1, signer, name = "new_admin", desc = "Pending admin")]
+    AcceptAdmin = 51,
+    // Перевод старой записи о минте в формат V2
+    #[account(0, writable, name = "mint_record", desc = "PDA [\"minted\", round, wallet]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the realloc")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(4, name = "wallet", desc = "Wallet the record belongs to")]
+    MigrateMintRecord(MigrateMintRecordArgsV1) = 52,
+    // Создание битовой карты минтов раунда
+    #[account(0, writable, name = "claim_bitmap", desc = "Claim bitmap PDA [\"claims\", round]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(4, name = "round_config", desc = "Round config PDA [\"round\", round], must not have started")]
+    CreateClaimBitmap(CreateClaimBitmapArgsV1) = 53,
+    // Постановка на паузу
+    #[account(0, writable, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(1, signer, name = "admin", desc = "Program admin")]
+    #[account(2, optional, writable, name = "round_config", desc = "Round config PDA, only when pausing a single round")]
+    Pause(PauseArgsV1) = 54,
+    // Снятие паузы
+    #[account(0, writable, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(1, signer, name = "admin", desc = "Program admin")]
+    #[account(2, optional, writable, name = "round_config", desc = "Round config PDA, only when unpausing a single round")]
+    Unpause(PauseArgsV1) = 55,
+    // Создание или замена распределения выручки
+    #[account(0, writable, name = "split_config", desc = "Split config PDA [\"split\"]")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    SetSplitConfig(SetSplitConfigArgsV1) = 57,
+    // Распределение баланса PDA между получателями (без подписи администратора)
+    #[account(0, writable, name = "split_config", desc = "Split config PDA [\"split\"]")]
+    #[account(1, writable, name = "source", desc = "Treasury PDA [\"treasury\"] or PDA [\"mint_authority\"]")]
+    #[account(2, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, writable, name = "recipients", desc = "Split recipients in config order (remaining accounts)")]
+    Distribute = 58,
+    // Пакетное обновление метаданных, результат по каждому NFT - в return data
+    #[account(0, name = "program_authority", desc = "PDA [\"mint_authority\"], update authority")]
+    #[account(1, writable, signer, name = "admin", desc = "Program admin, pays for the update")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(4, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(5, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    #[account(6, writable, name = "metadata_accounts", desc = "Metadata and mint pairs in updates order (remaining accounts)")]
+    BatchUpdateMetadata(BatchUpdateMetadataArgsV1) = 59,
+    // Отложенное раскрытие URI NFT по манифесту раунда (без подписи администратора)
+    #[account(0, writable, name = "metadata", desc = "Metadata account of the NFT")]
+    #[account(1, name = "mint", desc = "NFT mint")]
+    #[account(2, name = "program_authority", desc = "PDA [\"mint_authority\"], update authority")]
+    #[account(3, writable, signer, name = "payer", desc = "Any wallet, pays for the update")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(6, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(7, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(8, name = "program_config", desc = "Program config PDA [\"config\"]")]
+    Reveal(RevealArgsV1) = 60,
+    // Минт нескольких pNFT одного кошелька по мультидоказательству Merkle
+    #[account(0, writable, name = "program_authority", desc = "PDA [\"mint_authority\"]")]
+    #[account(1, writable, signer, name = "payer", desc = "Allowlisted wallet, pays for the mints and owns the tokens")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(4, name = "spl_token_program", desc = "Token program of the round: SPL Token or Token-2022")]
+    #[account(5, name = "associated_token_program", desc = "Associated Token Account program")]
+    #[account(6, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(7, name = "rent", desc = "Rent sysvar")]
+    #[account(8, name = "collection_mint", desc = "Collection mint")]
+    #[account(9, writable, name = "collection_metadata", desc = "Collection metadata")]
+    #[account(10, name = "collection_master_edition", desc = "Collection master edition")]
+    #[account(11, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(12, writable, name = "claim_bitmap", desc = "Claim bitmap PDA [\"claims\", round]")]
+    #[account(13, name = "program_config", desc = "Program config PDA [\"config\"], global pause flags")]
+    #[account(14, writable, name = "treasury", desc = "Treasury PDA [\"treasury\"], receives the mint price")]
+    #[account(15, writable, name = "nft_accounts", desc = "Metadata, master edition, mint (signer), token account and token record per NFT number (remaining accounts)")]
+    #[account(16, optional, writable, name = "payment_mint", desc = "Payment token mint after the NFT accounts, only when the round has a token price")]
+    #[account(17, optional, writable, name = "payment_token_account", desc = "Payer token account of the payment mint")]
+    #[account(18, optional, writable, name = "payment_vault", desc = "Treasury ATA of the payment mint, created if missing")]
+    #[account(19, optional, name = "payment_token_program", desc = "SPL Token or Token-2022 program of the payment mint")]
+    MintWithMultiproof(MintWithMultiproofArgsV1) = 61,
+    // Минт pNFT в пределах лимита кошелька из листа Merkle (несколькими транзакциями)
+    #[account(0, writable, name = "program_authority", desc = "PDA [\"mint_authority\"]")]
+    #[account(1, writable, signer, name = "payer", desc = "Allowlisted wallet, pays for the mints and owns the tokens")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "sysvar_instructions", desc = "Instructions sysvar")]
+    #[account(4, name = "spl_token_program", desc = "Token program of the round: SPL Token or Token-2022")]
+    #[account(5, name = "associated_token_program", desc = "Associated Token Account program")]
+    #[account(6, name = "token_metadata_program", desc = "Token Metadata program")]
+    #[account(7, name = "rent", desc = "Rent sysvar")]
+    #[account(8, name = "collection_mint", desc = "Collection mint")]
+    #[account(9, writable, name = "collection_metadata", desc = "Collection metadata")]
+    #[account(10, name = "collection_master_edition", desc = "Collection master edition")]
+    #[account(11, name = "round_config", desc = "Round config PDA [\"round\", round]")]
+    #[account(12, writable, name = "allowance_record", desc = "PDA [\"allowance\", round, payer], created on the first mint")]
+    #[account(13, name = "program_config", desc = "Program config PDA [\"config\"], global pause flags")]
+    #[account(14, writable, name = "treasury", desc = "Treasury PDA [\"treasury\"], receives the mint price")]
+    #[account(15, writable, name = "nft_accounts", desc = "Metadata, master edition, mint (signer), token account and token record per minted NFT (remaining accounts)")]
+    #[account(16, optional, writable, name = "payment_mint", desc = "Payment token mint after the NFT accounts, only when the round has a token price")]
+    #[account(17, optional, writable, name = "payment_token_account", desc = "Payer token account of the payment mint")]
+    #[account(18, optional, writable, name = "payment_vault", desc = "Treasury ATA of the payment mint, created if missing")]
+    #[account(19, optional, name = "payment_token_program", desc = "SPL Token or Token-2022 program of the payment mint")]
+    MintWithAllowance(MintWithAllowanceArgsV1) = 62,
+}
+
+impl RInstruction {
+    // Единственная точка разбора данных инструкции
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ProgramError> {
+        if instruction_data.is_empty() {
+            msg!("Instruction data is empty");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        borsh::from_slice(instruction_data).map_err(|err| {
+            msg!("Failed to parse instruction {}: {}", instruction_data[0], err);
+            ProgramError::InvalidInstructionData
+        })
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("Borsh serialization into a Vec does not fail")
+    }
+}
+
+// Данные инструкции 14 по версиям
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub enum ManualMintRecordArgs {
+    V1(ManualMintRecordArgsV1),
+    V2(ManualMintRecordArgsV2),
+}
+
+impl ManualMintRecordArgs {
+    pub fn round(&self) -> u64 {
+        match self {
+            ManualMintRecordArgs::V1(args) => args.round,
+            ManualMintRecordArgs::V2(args) => args.round,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct ManualMintRecordArgsV1 {
+    pub round: u64,
+    pub mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct ManualMintRecordArgsV2 {
+    pub round: u64,
+    pub mint: Pubkey,
+    pub nft_number: u16,
+}
+
+// В V1 нет NFTnumber: запись создается с NFTnumber 0
+impl From<ManualMintRecordArgs> for ManualMintRecordArgsV2 {
+    fn from(args: ManualMintRecordArgs) -> Self {
+        match args {
+            ManualMintRecordArgs::V1(args) => ManualMintRecordArgsV2 {
+                round: args.round,
+                mint: args.mint,
+                nft_number: 0,
+            },
+            ManualMintRecordArgs::V2(args) => args,
+        }
+    }
+}
+
+// Данные инструкции 15
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct DeleteMintRecordArgsV1 {
+    pub round: u64,
+}
+
+// Создатель NFT: доля в процентах, в сумме 100
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct CreatorArgsV1 {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+// Данные инструкции 39
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct CreateCollectionArgsV1 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    // Пустой список - PDA mint_authority со 100%
+    pub creators: Vec<CreatorArgsV1>,
+    // Начальный размер коллекции (CollectionDetails::V1)
+    pub collection_size: u64,
+}
+
+// Данные инструкции 42
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawArgsV1 {
+    // 0 - вывести все, что выше минимума для ренты (SOL) или весь баланс (токены)
+    pub amount: u64,
+}
+
+// Данные инструкции 43 по версиям: None - поле остается как в текущих метаданных
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub enum UpdateMetadataArgs {
+    V1(UpdateMetadataArgsV1),
+    V2(UpdateMetadataArgsV2),
+}
+
+impl UpdateMetadataArgs {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            UpdateMetadataArgs::V1(args) => args.mint,
+            UpdateMetadataArgs::V2(args) => args.mint,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct UpdateMetadataArgsV1 {
+    pub mint: Pubkey,
+    // Единственный создатель со 100%
+    pub creator: Option<Pubkey>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub seller_fee_basis_points: Option<u16>,
+}
+
+impl From<UpdateMetadataArgs> for UpdateMetadataArgsV2 {
+    fn from(args: UpdateMetadataArgs) -> Self {
+        match args {
+            UpdateMetadataArgs::V1(args) => UpdateMetadataArgsV2 {
+                mint: args.mint,
+                creators: args.creator.map(|address| vec![CreatorArgsV1 { address, share: 100 }]),
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                seller_fee_basis_points: args.seller_fee_basis_points,
+                primary_sale_happened: None,
+                is_mutable: None,
+            },
+            UpdateMetadataArgs::V2(args) => args,
+        }
+    }
+}
+
+// Также элемент инструкции 59
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct UpdateMetadataArgsV2 {
+    pub mint: Pubkey,
+    // Новый список создателей целиком, доли в сумме 100
+    pub creators: Option<Vec<CreatorArgsV1>>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub seller_fee_basis_points: Option<u16>,
+    // Token Metadata допускает только переход false -> true
+    pub primary_sale_happened: Option<bool>,
+    // false делает метаданные неизменяемыми навсегда
+    pub is_mutable: Option<bool>,
+}
+
+// Данные инструкции 59: по паре аккаунтов metadata/mint на каждое обновление
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct BatchUpdateMetadataArgsV1 {
+    pub updates: Vec<UpdateMetadataArgsV2>,
+}
+
+// Результат одного обновления инструкции 59; return data - Vec<BatchUpdateStatus>
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, Copy, PartialEq, Eq)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum BatchUpdateStatus {
+    Updated = 0,
+    // Проверка до CPI не прошла, метаданные не изменены. Ошибка CPI UpdateV1
+    // этим статусом не возвращается: она отменяет всю транзакцию
+    Failed = 1,
+    // Не хватило вычислительного бюджета, обновление не выполнялось
+    Skipped = 2,
+}
+
+// Данные инструкции 45
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct MintWithProofArgsV1 {
+    pub round: u64,
+    pub nft_number: u16,
+    pub proof: Vec<[u8; 32]>,
+}
+
+// Данные инструкций 47 и 48 по версиям
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub enum RoundConfigArgs {
+    V1(RoundConfigArgsV1),
+    V2(RoundConfigArgsV2),
+}
+
+impl RoundConfigArgs {
+    pub fn round(&self) -> u64 {
+        match self {
+            RoundConfigArgs::V1(args) => args.round,
+            RoundConfigArgs::V2(args) => args.round,
+        }
+    }
+
+    // Параметры, которых нет в V1, берутся из base: при создании - значения по
+    // умолчанию, при обновлении - текущая конфигурация раунда
+    pub fn into_v2(self, base: RoundConfigArgsV2) -> RoundConfigArgsV2 {
+        match self {
+            RoundConfigArgs::V1(args) => RoundConfigArgsV2 {
+                round: args.round,
+                merkle_root: args.merkle_root,
+                collection_mint: args.collection_mint,
+                name_template: args.name_template,
+                symbol: args.symbol,
+                uri_base: args.uri_base,
+                seller_fee_basis_points: args.seller_fee_basis_points,
+                ..base
+            },
+            RoundConfigArgs::V2(args) => args,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct RoundConfigArgsV1 {
+    pub round: u64,
+    pub merkle_root: [u8; 32],
+    // Pubkey::default() - коллекция программы (инструкция 39)
+    pub collection_mint: Pubkey,
+    pub name_template: String,
+    pub symbol: String,
+    pub uri_base: String,
+    pub seller_fee_basis_points: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoundConfigArgsV2 {
+    pub round: u64,
+    pub merkle_root: [u8; 32],
+    // Pubkey::default() - коллекция программы (инструкция 39)
+    pub collection_mint: Pubkey,
+    // Шаблоны с плейсхолдерами {nft_number} и {round}, см. RoundConfig
+    pub name_template: String,
+    pub symbol: String,
+    pub uri_base: String,
+    pub seller_fee_basis_points: u16,
+    // Окно минта (unix timestamp); 0 - без ограничения
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    // Цена минта в лампортах; 0 - бесплатно
+    pub price_lamports: u64,
+    // Цена минта в токене (price_amount = 0 - без оплаты токеном)
+    pub price_mint: Pubkey,
+    pub price_amount: u64,
+    // Доля оплаты токеном, которая сжигается (базисные пункты)
+    pub burn_basis_points: u16,
+    // SPL Token или Token-2022 для NFT раунда; Pubkey::default() - SPL Token
+    pub token_program: Pubkey,
+    // Корень манифеста раскрытия; нули - без раскрытия
+    pub reveal_root: [u8; 32],
+    // Формат листьев merkle_root
+    pub leaf_version: MerkleLeafVersion,
+}
+
+impl RoundConfigArgsV2 {
+    pub fn into_config(self, bump: u8) -> RoundConfig {
+        RoundConfig {
+            key: Key::RoundConfig,
+            round: self.round,
+            merkle_root: self.merkle_root,
+            collection_mint: self.collection_mint,
+            name_template: self.name_template,
+            symbol: self.symbol,
+            uri_base: self.uri_base,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            bump,
+            start_timestamp: self.start_timestamp,
+            end_timestamp: self.end_timestamp,
+            paused: 0,
+            price_lamports: self.price_lamports,
+            price_mint: self.price_mint,
+            price_amount: self.price_amount,
+            burn_basis_points: self.burn_basis_points,
+            token_program: self.token_program,
+            reveal_root: self.reveal_root,
+            leaf_version: self.leaf_version,
+        }
+    }
+}
+
+impl From<&RoundConfig> for RoundConfigArgsV2 {
+    fn from(config: &RoundConfig) -> Self {
+        RoundConfigArgsV2 {
+            round: config.round,
+            merkle_root: config.merkle_root,
+            collection_mint: config.collection_mint,
+            name_template: config.name_template.clone(),
+            symbol: config.symbol.clone(),
+            uri_base: config.uri_base.clone(),
+            seller_fee_basis_points: config.seller_fee_basis_points,
+            start_timestamp: config.start_timestamp,
+            end_timestamp: config.end_timestamp,
+            price_lamports: config.price_lamports,
+            price_mint: config.price_mint,
+            price_amount: config.price_amount,
+            burn_basis_points: config.burn_basis_points,
+            token_program: config.token_program,
+            reveal_root: config.reveal_root,
+            leaf_version: config.leaf_version,
+        }
+    }
+}
+
+// Данные инструкции 60: URI из манифеста раскрытия и доказательство против
+// RoundConfig.reveal_root (лист - merkle::reveal_leaf)
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct RevealArgsV1 {
+    pub round: u64,
+    pub nft_number: u16,
+    pub uri: String,
+    pub proof: Vec<[u8; 32]>,
+}
+
+// Данные инструкции 61: NFTnumber кошелька и индексы их листьев в дереве раунда
+// из leaf_count листьев, proof - общие узлы (см. merkle::verify_merkle_multiproof)
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct MintWithMultiproofArgsV1 {
+    pub round: u64,
+    pub nft_numbers: Vec<u16>,
+    pub leaf_indices: Vec<u32>,
+    pub leaf_count: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+// Данные инструкции 62: все NFTnumber кошелька из его листа (лимит - их количество)
+// и сколько из них минтить сейчас; минтятся следующие по счетчику AllowanceRecord.minted
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct MintWithAllowanceArgsV1 {
+    pub round: u64,
+    pub nft_numbers: Vec<u16>,
+    pub quantity: u16,
+    pub proof: Vec<[u8; 32]>,
+}
+
+// Данные инструкции 49
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct InitializeProgramConfigArgsV1 {
+    pub admin: Pubkey,
+}
+
+// Данные инструкции 50
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct ProposeAdminArgsV1 {
+    pub new_admin: Pubkey,
+}
+
+// Данные инструкции 52
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct MigrateMintRecordArgsV1 {
+    pub round: u64,
+    pub nft_number: u16,
+}
+
+// Данные инструкции 53
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct CreateClaimBitmapArgsV1 {
+    pub round: u64,
+    // Количество бит: NFTnumber должен быть меньше capacity
+    pub capacity: u32,
+}
+
+// Данные инструкций 54 и 55
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct PauseArgsV1 {
+    // Маска областей: PAUSE_MINT, PAUSE_UPDATE, PAUSE_WITHDRAW
+    pub scope: u8,
+    // None - глобальная пауза, Some - пауза одного раунда
+    pub round: Option<u64>,
+}
+
+// Данные инструкции 57
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, PartialEq, Eq)]
+pub struct SetSplitConfigArgsV1 {
+    // Доли в базисных пунктах, в сумме 10000
+    pub recipients: Vec<SplitRecipient>,
+}
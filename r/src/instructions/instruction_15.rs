@@ -8,10 +8,14 @@ use solana_program::{
     msg,
 };
 
+use crate::error::RError;
+use crate::instruction::DeleteMintRecordArgsV1;
+use crate::state::ProgramConfig;
+
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    args: DeleteMintRecordArgsV1,
 ) -> ProgramResult {
     msg!("Deleting mint record account for specific round...");
     
@@ -20,12 +24,12 @@ pub fn process_instruction(
     // Получаем необходимые аккаунты
     let payer = next_account_info(account_info_iter)?;
     let mint_record_account = next_account_info(account_info_iter)?;
+    let program_config_account = next_account_info(account_info_iter)?;
+    let wallet = next_account_info(account_info_iter)?;
 
-    // Проверяем подпись
-    if !payer.is_signer {
-        msg!("Payer must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    // Удалить запись о минте может только администратор (он же получает ламппорты)
+    ProgramConfig::assert_admin(program_config_account, payer, program_id)?;
+    msg!("Wallet: {}", wallet.key);
 
     // Проверяем владельца аккаунта
     if mint_record_account.owner != program_id {
@@ -33,12 +37,7 @@ pub fn process_instruction(
         return Err(ProgramError::IncorrectProgramId);
     }
     
-    // Получаем номер раунда из данных инструкции
-    if instruction_data.is_empty() {
-        msg!("Invalid instruction data: missing round number");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-    let round_number = instruction_data[0] as u64;
+    let round_number = args.round;
     msg!("Deleting mint record for round {}", round_number);
     
     // Проверяем только расширенный PDA
@@ -46,7 +45,7 @@ pub fn process_instruction(
         &[
             b"minted",
             &round_number.to_le_bytes(),
-            payer.key.as_ref(),
+            wallet.key.as_ref(),
         ],
         program_id
     );
@@ -56,7 +55,7 @@ pub fn process_instruction(
         msg!("Invalid mint record account address");
         msg!("Expected: {}", expected_mint_record_address);
         msg!("Received: {}", mint_record_account.key);
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidMintRecordAddress.into());
     }
     
     msg!("Deleting mint record account");
@@ -1,4 +1,8 @@
-// Создание NFT-коллекции стандартного токена (SPL Token) с чеканкой на АТА программы
+// Инструкция 39: Создание NFT-коллекции с чеканкой на АТА программы
+//
+// Название, символ, URI, роялти, создатели и размер коллекции передаются в данных
+// инструкции. Минт коллекции записывается в конфигурацию программы: по нему
+// инструкции минта проверяют collection_mint.
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -14,19 +18,28 @@ use solana_program::{
 };
 use mpl_token_metadata::{
     instructions::{CreateV1, CreateV1InstructionArgs, MintV1, MintV1InstructionArgs},
-    types::{TokenStandard, PrintSupply, Collection, CollectionDetails, Creator},
+    types::{TokenStandard, PrintSupply, CollectionDetails, Creator},
+};
+
+use crate::error::RError;
+use crate::instruction::{CreateCollectionArgsV1, CreatorArgsV1};
+use crate::instructions::instruction_45::create_nft_mint;
+use crate::state::{
+    ProgramConfig, MAX_CREATORS, MAX_NAME_TEMPLATE_LENGTH, MAX_SELLER_FEE_BASIS_POINTS,
+    MAX_SYMBOL_LENGTH, MAX_URI_BASE_LENGTH,
 };
-use spl_token::ID as TOKEN_PROGRAM_ID;
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    _instruction_data: &[u8],
+    args: CreateCollectionArgsV1,
 ) -> ProgramResult {
-    msg!("Starting create_standard_token_to_program_ata...");
-    
+    msg!("Instruction 39: Create collection");
+    msg!("Name: {}", args.name);
+    msg!("Collection size: {}", args.collection_size);
+
     let accounts_iter = &mut accounts.iter();
-    
+
     // Получаем все необходимые аккаунты
     let metadata_account = next_account_info(accounts_iter)?;
     let master_edition_account = next_account_info(accounts_iter)?;
@@ -41,23 +54,22 @@ pub fn process_instruction(
     let spl_ata_program = next_account_info(accounts_iter)?;
     let token_metadata_program = next_account_info(accounts_iter)?;
     let rent_sysvar = next_account_info(accounts_iter)?;
-    
+    let program_config_account = next_account_info(accounts_iter)?;
+
+    // Создавать коллекцию может только администратор, он же платит за аккаунты
+    let mut program_config = ProgramConfig::assert_admin(program_config_account, payer, program_id)?;
+
     // Проверяем подписи
     if !mint_account.is_signer {
         msg!("Mint account must be a signer");
         return Err(ProgramError::MissingRequiredSignature);
     }
-    if !payer.is_signer {
-        msg!("Payer must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
 
-    // Проверяем, что token_program имеет правильный ID
-    if token_program.key != &TOKEN_PROGRAM_ID {
-        msg!("Invalid Token program ID provided");
-        msg!("Expected: {}", TOKEN_PROGRAM_ID);
+    // Коллекция может быть SPL Token или Token-2022
+    if token_program.key != &spl_token::ID && token_program.key != &spl_token_2022::ID {
+        msg!("Token program must be SPL Token or Token-2022");
         msg!("Received: {}", token_program.key);
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidTokenProgram.into());
     }
 
     // Проверяем, что program_authority это правильный PDA
@@ -69,9 +81,38 @@ pub fn process_instruction(
         msg!("Invalid program authority provided");
         msg!("Expected: {}", expected_authority);
         msg!("Received: {}", program_authority.key);
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidProgramAuthority.into());
     }
 
+    // Проверяем ограничения Token Metadata на строки и комиссию
+    if args.name.len() > MAX_NAME_TEMPLATE_LENGTH {
+        msg!("Name is too long: {} > {}", args.name.len(), MAX_NAME_TEMPLATE_LENGTH);
+        return Err(RError::NameTooLong.into());
+    }
+    if args.symbol.len() > MAX_SYMBOL_LENGTH {
+        msg!("Symbol is too long: {} > {}", args.symbol.len(), MAX_SYMBOL_LENGTH);
+        return Err(RError::SymbolTooLong.into());
+    }
+    if args.uri.len() > MAX_URI_BASE_LENGTH {
+        msg!("URI is too long: {} > {}", args.uri.len(), MAX_URI_BASE_LENGTH);
+        return Err(RError::UriTooLong.into());
+    }
+    if args.seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+        msg!("Seller fee basis points must be <= {}", MAX_SELLER_FEE_BASIS_POINTS);
+        return Err(RError::InvalidSellerFeeBasisPoints.into());
+    }
+
+    // Создатели: без списка - PDA программы со 100%
+    let creators = if args.creators.is_empty() {
+        vec![Creator {
+            address: *program_authority.key,
+            verified: true,
+            share: 100,
+        }]
+    } else {
+        build_creators(&args.creators, program_authority.key, &[])?
+    };
+
     // Создаем authority seeds для подписи
     let authority_signature_seeds = &[
         b"mint_authority".as_ref(),
@@ -79,61 +120,24 @@ pub fn process_instruction(
     ];
     let signers = &[&authority_signature_seeds[..]];
 
-    // Создаем минт аккаунт с использованием стандартного SPL Token
-    msg!("Creating mint account with standard SPL Token...");
-    let rent = Rent::get()?;
-    let mint_len = 82; // Размер аккаунта Mint в байтах
-    let lamports = rent.minimum_balance(mint_len);
-
     // Проверяем, что mint аккаунт еще не инициализирован
     if mint_account.data_len() > 0 {
         msg!("Mint account already initialized");
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
-    // Создаем аккаунт для минта
-    invoke(
-        &system_instruction::create_account(
-            payer.key,
-            mint_account.key,
-            lamports,
-            mint_len as u64,
-            &TOKEN_PROGRAM_ID,
-        ),
-        &[
-            payer.clone(),
-            mint_account.clone(),
-            system_program.clone(),
-        ],
-    )?;
-
-    // Инициализируем минт с использованием стандартного SPL Token
-    msg!("Initializing mint with standard SPL Token...");
-    let init_mint_ix = spl_token::instruction::initialize_mint(
-        &TOKEN_PROGRAM_ID,
-        mint_account.key,
-        program_authority.key,
-        Some(program_authority.key),
-        0,
-    )?;
-    
-    invoke(
-        &init_mint_ix,
-        &[
-            mint_account.clone(),
-            rent_sysvar.clone(),
-        ],
+    // Создаем и инициализируем минт коллекции
+    let rent = Rent::get()?;
+    create_nft_mint(
+        payer,
+        mint_account,
+        metadata_account,
+        program_authority,
+        token_program,
+        system_program,
+        &rent,
     )?;
 
-    // Создаем список создателей с program_id в качестве создателя с долей 100%
-    let creators = vec![
-        Creator {
-            address: *program_id,
-            verified: false,
-            share: 100,
-        },
-    ];
-
     // Создаем метаданные и master edition для токена
     msg!("Creating metadata and master edition for token...");
     let create_v1 = CreateV1 {
@@ -145,21 +149,21 @@ pub fn process_instruction(
         update_authority: (*program_authority.key, true),
         system_program: *system_program.key,
         sysvar_instructions: *sysvar_instructions.key,
-        spl_token_program: Some(*token_program.key), // Используем стандартный SPL Token
+        spl_token_program: Some(*token_program.key),
     };
 
     let create_args = CreateV1InstructionArgs {
-        name: "Yapster Infinity".to_string(),
-        symbol: "YAP8v1".to_string(),
-        uri: "https://a.b/c.json".to_string(),
-        seller_fee_basis_points: 1000,
+        name: args.name,
+        symbol: args.symbol,
+        uri: args.uri,
+        seller_fee_basis_points: args.seller_fee_basis_points,
         creators: Some(creators),
         primary_sale_happened: false,
         is_mutable: true,
         token_standard: TokenStandard::ProgrammableNonFungible,
         collection: None,
         uses: None,
-        collection_details: Some(CollectionDetails::V1 { size: 0 }), // Устанавливаем флаг коллекции
+        collection_details: Some(CollectionDetails::V1 { size: args.collection_size }), // Устанавливаем флаг коллекции
         rule_set: None,
         decimals: Some(0),
         print_supply: Some(PrintSupply::Zero),
@@ -182,14 +186,14 @@ pub fn process_instruction(
         signers,
     )?;
 
-    // Создаем ассоциированный токен аккаунт для программы с использованием стандартного SPL Token
-    msg!("Creating associated token account for program with standard SPL Token...");
+    // Создаем ассоциированный токен аккаунт для программы
+    msg!("Creating associated token account for program...");
     invoke(
         &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
             payer.key,
             program_authority.key, // Владелец токена - программа (PDA)
             mint_account.key,
-            &TOKEN_PROGRAM_ID,
+            token_program.key,
         ),
         &[
             payer.clone(),
@@ -203,8 +207,8 @@ pub fn process_instruction(
         ],
     )?;
 
-    // Минтим токен с использованием стандартного SPL Token на АТА программы
-    msg!("Minting token to program's ATA with standard SPL Token...");
+    // Минтим токен на АТА программы
+    msg!("Minting token to program's ATA...");
     let mint_v1 = MintV1 {
         token: *token_account.key,
         token_owner: Some(*program_authority.key), // Владелец токена - программа (PDA)
@@ -216,7 +220,7 @@ pub fn process_instruction(
         payer: *payer.key,
         system_program: *system_program.key,
         sysvar_instructions: *sysvar_instructions.key,
-        spl_token_program: *token_program.key, // Используем стандартный SPL Token
+        spl_token_program: *token_program.key,
         spl_ata_program: *spl_ata_program.key,
         authorization_rules_program: None,
         authorization_rules: None,
@@ -249,6 +253,66 @@ pub fn process_instruction(
         signers,
     )?;
 
-    msg!("Standard token created and minted successfully to program's ATA!");
+    // Конфигурации, созданные до добавления collection_mint, короче LEN:
+    // доплачиваем ренту и увеличиваем аккаунт
+    if program_config_account.data_len() < ProgramConfig::LEN {
+        let required_lamports = rent.minimum_balance(ProgramConfig::LEN);
+        let current_lamports = program_config_account.lamports();
+        if current_lamports < required_lamports {
+            let top_up = required_lamports - current_lamports;
+            msg!("Topping up program config rent: {} lamports", top_up);
+            invoke(
+                &system_instruction::transfer(payer.key, program_config_account.key, top_up),
+                &[
+                    payer.clone(),
+                    program_config_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        program_config_account.realloc(ProgramConfig::LEN, false)?;
+    }
+
+    // Запоминаем коллекцию: по ней инструкции минта проверяют collection_mint
+    program_config.collection_mint = *mint_account.key;
+    program_config.save(program_config_account)?;
+
+    msg!("Collection {} created and minted successfully to program's ATA!", mint_account.key);
     Ok(())
-} 
\ No newline at end of file
+}
+
+// Проверяет список создателей и собирает его для Token Metadata. PDA подписывает
+// CreateV1/UpdateV1, поэтому верифицированным может стать только он; уже
+// верифицированные создатели из existing сохраняют подпись
+pub(crate) fn build_creators(
+    creators: &[CreatorArgsV1],
+    program_authority: &Pubkey,
+    existing: &[Creator],
+) -> Result<Vec<Creator>, ProgramError> {
+    if creators.is_empty() || creators.len() > MAX_CREATORS {
+        msg!("Creators count must be 1..={}, got {}", MAX_CREATORS, creators.len());
+        return Err(RError::InvalidCreators.into());
+    }
+    for (i, creator) in creators.iter().enumerate() {
+        if creators[..i].iter().any(|other| other.address == creator.address) {
+            msg!("Duplicate creator: {}", creator.address);
+            return Err(RError::InvalidCreators.into());
+        }
+    }
+    let total_share: u32 = creators.iter().map(|creator| creator.share as u32).sum();
+    if total_share != 100 {
+        msg!("Creator shares must sum to 100, got {}", total_share);
+        return Err(RError::InvalidCreators.into());
+    }
+    Ok(creators
+        .iter()
+        .map(|creator| Creator {
+            address: creator.address,
+            verified: creator.address == *program_authority
+                || existing
+                    .iter()
+                    .any(|old| old.address == creator.address && old.verified),
+            share: creator.share,
+        })
+        .collect())
+}
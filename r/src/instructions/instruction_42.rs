@@ -1,112 +1,207 @@
-// Инструкция 42: Отправка SOL с PDA программы на указанный адрес
+// Инструкция 42: Вывод SOL или токенов с PDA программы на адрес из списка аккаунтов
+//
+// Источник - PDA ["mint_authority"] или казна ["treasury"]. Без аккаунта минта
+// выводятся лампорты, на PDA остается минимум для ренты. С аккаунтом минта
+// выводятся токены с ассоциированного счета PDA на ассоциированный счет получателя.
+// Каждый вывод пишет событие REvent::Withdraw для сверки учета.
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    program::invoke_signed,
-    program_error::ProgramError,
+    program::{invoke, invoke_signed},
     pubkey::Pubkey,
     system_instruction,
     msg,
+    sysvar::clock::Clock,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    instruction::transfer_checked,
+    state::{Account, Mint},
 };
 
-// Фиксированный адрес получателя
-const RECIPIENT_ADDRESS: &str = "GDi7rtknaEdvgGrm9qpXbF54ZGZMGezmXLky2VQac2c6";
+use crate::error::RError;
+use crate::events::{REvent, WithdrawEvent};
+use crate::instruction::WithdrawArgsV1;
+use crate::state::{assert_not_paused, assert_withdraw_source, ProgramConfig, PAUSE_WITHDRAW};
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    args: WithdrawArgsV1,
 ) -> ProgramResult {
-    msg!("Starting withdraw_sol...");
-    
-    // Парсим сумму для отправки из instruction_data
-    if instruction_data.len() < 8 {
-        msg!("Error: Instruction data must contain amount to send (8 bytes)");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-    
-    let amount_to_send = u64::from_le_bytes(
-        instruction_data[0..8].try_into().map_err(|_| {
-            msg!("Error: Failed to parse amount from instruction data");
-            ProgramError::InvalidInstructionData
-        })?
-    );
-    
-    msg!("Amount to send: {} lamports", amount_to_send);
-    
+    msg!("Instruction 42: Withdraw from program PDA");
+
     let accounts_iter = &mut accounts.iter();
-    
+
     // Получаем все необходимые аккаунты
-    let program_authority = next_account_info(accounts_iter)?;
+    let source = next_account_info(accounts_iter)?;
     let recipient = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
-    
-    // Проверяем, что recipient имеет правильный адрес
-    let expected_recipient = match Pubkey::try_from(RECIPIENT_ADDRESS) {
-        Ok(pubkey) => pubkey,
+    let program_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+
+    // Вывод средств доступен только администратору
+    let program_config = ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_WITHDRAW)?;
+
+    // Источник - PDA программы или казна
+    let (source_seed, bump_seed) = assert_withdraw_source(source, program_id)?;
+    let source_signature_seeds = &[source_seed, &[bump_seed]];
+    let signers = &[&source_signature_seeds[..]];
+
+    // Аккаунт минта передается только при выводе токенов
+    let (mint, amount, remaining) = match next_account_info(accounts_iter) {
+        Ok(mint) => {
+            let source_token_account = next_account_info(accounts_iter)?;
+            let recipient_token_account = next_account_info(accounts_iter)?;
+            let token_program = next_account_info(accounts_iter)?;
+            let spl_ata_program = next_account_info(accounts_iter)?;
+
+            if token_program.key != &spl_token::ID && token_program.key != &spl_token_2022::ID {
+                msg!("Token program must be SPL Token or Token-2022");
+                return Err(RError::InvalidTokenProgram.into());
+            }
+            if mint.owner != token_program.key {
+                msg!("Mint does not belong to the token program");
+                return Err(RError::InvalidTokenProgram.into());
+            }
+
+            let expected_source_token_account =
+                get_associated_token_address_with_program_id(source.key, mint.key, token_program.key);
+            if source_token_account.key != &expected_source_token_account {
+                msg!("Invalid source token account provided");
+                msg!("Expected: {}", expected_source_token_account);
+                msg!("Received: {}", source_token_account.key);
+                return Err(RError::InvalidTokenAccount.into());
+            }
+            let expected_recipient_token_account =
+                get_associated_token_address_with_program_id(recipient.key, mint.key, token_program.key);
+            if recipient_token_account.key != &expected_recipient_token_account {
+                msg!("Invalid recipient token account provided");
+                msg!("Expected: {}", expected_recipient_token_account);
+                msg!("Received: {}", recipient_token_account.key);
+                return Err(RError::InvalidTokenAccount.into());
+            }
+
+            // Разбор подходит и для SPL Token, и для Token-2022 (с расширениями)
+            let decimals = {
+                let mint_data = mint.try_borrow_data()?;
+                StateWithExtensions::<Mint>::unpack(&mint_data)?.base.decimals
+            };
+            let available = {
+                let token_account_data = source_token_account.try_borrow_data()?;
+                StateWithExtensions::<Account>::unpack(&token_account_data)?.base.amount
+            };
+
+            // 0 - выводим весь баланс
+            let amount = if args.amount == 0 { available } else { args.amount };
+            if amount == 0 {
+                msg!("Nothing to withdraw");
+                return Err(RError::InvalidWithdrawAmount.into());
+            }
+            if amount > available {
+                msg!("Insufficient tokens in source token account");
+                msg!("Available: {}", available);
+                msg!("Required: {}", amount);
+                return Err(RError::InsufficientPdaFunds.into());
+            }
+
+            // Счет получателя создается за счет администратора, если его еще нет
+            invoke(
+                &create_associated_token_account_idempotent(
+                    admin.key,
+                    recipient.key,
+                    mint.key,
+                    token_program.key,
+                ),
+                &[
+                    admin.clone(),
+                    recipient_token_account.clone(),
+                    recipient.clone(),
+                    mint.clone(),
+                    system_program.clone(),
+                    token_program.clone(),
+                    spl_ata_program.clone(),
+                ],
+            )?;
+
+            msg!("Sending {} tokens of {} from {} to {}", amount, mint.key, source.key, recipient.key);
+
+            invoke_signed(
+                &transfer_checked(
+                    token_program.key,
+                    source_token_account.key,
+                    mint.key,
+                    recipient_token_account.key,
+                    source.key,
+                    &[],
+                    amount,
+                    decimals,
+                )?,
+                &[
+                    source_token_account.clone(),
+                    mint.clone(),
+                    recipient_token_account.clone(),
+                    source.clone(),
+                    token_program.clone(),
+                ],
+                signers,
+            )?;
+
+            (Some(*mint.key), amount, available - amount)
+        }
         Err(_) => {
-            msg!("Failed to parse recipient address");
-            return Err(ProgramError::InvalidArgument);
+            // На PDA остается минимум для ренты; 0 - выводим все сверх минимума
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(source.data_len());
+            let available = source.lamports().saturating_sub(rent_exempt_minimum);
+            let amount = if args.amount == 0 { available } else { args.amount };
+            if amount == 0 {
+                msg!("Nothing to withdraw");
+                return Err(RError::InvalidWithdrawAmount.into());
+            }
+            if amount > available {
+                msg!("Insufficient funds in program PDA");
+                msg!("Available: {} lamports (rent-exempt minimum {})", available, rent_exempt_minimum);
+                msg!("Required: {} lamports", amount);
+                return Err(RError::InsufficientPdaFunds.into());
+            }
+
+            msg!("Sending {} lamports from {} to {}", amount, source.key, recipient.key);
+
+            invoke_signed(
+                &system_instruction::transfer(source.key, recipient.key, amount),
+                &[
+                    source.clone(),
+                    recipient.clone(),
+                    system_program.clone(),
+                ],
+                signers,
+            )?;
+
+            (None, amount, source.lamports())
         }
     };
-    
-    if recipient.key != &expected_recipient {
-        msg!("Invalid recipient address provided");
-        msg!("Expected: {}", expected_recipient);
-        msg!("Received: {}", recipient.key);
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    // Проверяем, что program_authority это правильный PDA
-    let (expected_authority, bump_seed) = Pubkey::find_program_address(
-        &[b"mint_authority"],
-        program_id
-    );
-    if program_authority.key != &expected_authority {
-        msg!("Invalid program authority provided");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    // Проверяем, что у PDA достаточно средств
-    if program_authority.lamports() < amount_to_send {
-        msg!("Insufficient funds in program authority PDA");
-        msg!("Available: {} lamports", program_authority.lamports());
-        msg!("Required: {} lamports", amount_to_send);
-        return Err(ProgramError::InsufficientFunds);
-    }
-
-    // Создаем authority seeds для подписи
-    let authority_signature_seeds = &[
-        b"mint_authority".as_ref(),
-        &[bump_seed],
-    ];
-    let signers = &[&authority_signature_seeds[..]];
-
-    // Создаем инструкцию для отправки SOL
-    let transfer_instruction = system_instruction::transfer(
-        program_authority.key,
-        recipient.key,
-        amount_to_send
-    );
-
-    msg!("Sending {} lamports from {} to {}", 
-        amount_to_send, 
-        program_authority.key, 
-        recipient.key
-    );
-
-    // Выполняем инструкцию с подписью PDA
-    invoke_signed(
-        &transfer_instruction,
-        &[
-            program_authority.clone(),
-            recipient.clone(),
-            system_program.clone(),
-        ],
-        signers,
-    )?;
-
-    msg!("Successfully sent {} lamports to {}", amount_to_send, recipient.key);
+
+    let clock = Clock::get()?;
+    REvent::Withdraw(WithdrawEvent {
+        source: *source.key,
+        recipient: *recipient.key,
+        admin: *admin.key,
+        mint,
+        amount,
+        remaining,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    })
+    .emit()?;
+
+    msg!("Successfully sent {} to {}", amount, recipient.key);
     Ok(())
-} 
\ No newline at end of file
+}
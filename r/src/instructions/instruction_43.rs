@@ -1,153 +1,70 @@
 // Инструкция 43: Универсальное обновление метаданных NFT
+//
+// Текущие метаданные читаются из аккаунта, заменяются только переданные поля.
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     program::invoke_signed,
-    program_error::ProgramError,
     pubkey::Pubkey,
     msg,
 };
 use mpl_token_metadata::{
+    accounts::Metadata,
     instructions::{UpdateV1, UpdateV1InstructionArgs},
-    types::{Creator, Data, CollectionToggle, RuleSetToggle, CollectionDetailsToggle, UsesToggle},
+    types::{Data, CollectionToggle, RuleSetToggle, CollectionDetailsToggle, UsesToggle},
+};
+
+use crate::error::RError;
+use crate::instruction::{UpdateMetadataArgs, UpdateMetadataArgsV2};
+use crate::instructions::instruction_39::build_creators;
+use crate::state::{
+    assert_not_paused, assert_token_metadata_program, ProgramConfig, MAX_NAME_TEMPLATE_LENGTH, MAX_SELLER_FEE_BASIS_POINTS,
+    MAX_SYMBOL_LENGTH, MAX_URI_BASE_LENGTH, PAUSE_UPDATE,
 };
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    args: UpdateMetadataArgs,
 ) -> ProgramResult {
     msg!("Starting universal_update_nft_metadata (instruction 43)...");
-    
-    // Парсим данные инструкции
-    if instruction_data.len() < 32 {
-        msg!("Error: Instruction data must contain at least the mint address (32 bytes)");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-    
-    // Первые 32 байта - адрес минта
-    let mint_address = Pubkey::new_from_array(
-        instruction_data[0..32].try_into().map_err(|_| {
-            msg!("Error: Failed to parse mint address from instruction data");
-            ProgramError::InvalidInstructionData
-        })?
-    );
+    let args = UpdateMetadataArgsV2::from(args);
+    let UpdateMetadataArgsV2 {
+        mint: mint_address,
+        creators,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        primary_sale_happened,
+        is_mutable,
+    } = &args;
     
     msg!("Target mint address: {}", mint_address);
-    
-    // Проверяем, есть ли данные о creator
-    let has_creator = instruction_data.len() > 32 && instruction_data[32] == 1;
-    let creator_address = if has_creator && instruction_data.len() >= 65 {
-        Some(Pubkey::new_from_array(
-            instruction_data[33..65].try_into().map_err(|_| {
-                msg!("Error: Failed to parse creator address from instruction data");
-                ProgramError::InvalidInstructionData
-            })?
-        ))
-    } else {
-        None
-    };
-    
-    if let Some(creator) = &creator_address {
-        msg!("Creator address to set: {}", creator);
-    }
-    
-    // Проверяем, есть ли данные о name, symbol, uri и sellerFeeBasisPoints
-    let has_metadata = instruction_data.len() > 65 && instruction_data[65] == 1;
-    
-    // Получаем name, symbol, uri и sellerFeeBasisPoints из instruction_data
-    let name = if has_metadata && instruction_data.len() > 66 {
-        let name_len = instruction_data[66] as usize;
-        if instruction_data.len() >= 67 + name_len {
-            let name_bytes = &instruction_data[67..67 + name_len];
-            match std::str::from_utf8(name_bytes) {
-                Ok(name_str) => {
-                    msg!("Name to set: {}", name_str);
-                    Some(name_str.to_string())
-                },
-                Err(_) => {
-                    msg!("Error: Failed to parse name from instruction data");
-                    None
-                }
-            }
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-    
-    // Получаем symbol
-    let symbol_offset = if let Some(name_str) = &name {
-        67 + name_str.len()
-    } else {
-        67
-    };
-    
-    let symbol = if has_metadata && instruction_data.len() > symbol_offset {
-        let symbol_len = instruction_data[symbol_offset] as usize;
-        if instruction_data.len() >= symbol_offset + 1 + symbol_len {
-            let symbol_bytes = &instruction_data[symbol_offset + 1..symbol_offset + 1 + symbol_len];
-            match std::str::from_utf8(symbol_bytes) {
-                Ok(symbol_str) => {
-                    msg!("Symbol to set: {}", symbol_str);
-                    Some(symbol_str.to_string())
-                },
-                Err(_) => {
-                    msg!("Error: Failed to parse symbol from instruction data");
-                    None
-                }
-            }
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-    
-    // Получаем uri
-    let uri_offset = if let Some(symbol_str) = &symbol {
-        symbol_offset + 1 + symbol_str.len()
-    } else {
-        symbol_offset
-    };
-    
-    let uri = if has_metadata && instruction_data.len() > uri_offset {
-        let uri_len = instruction_data[uri_offset] as usize;
-        if instruction_data.len() >= uri_offset + 1 + uri_len {
-            let uri_bytes = &instruction_data[uri_offset + 1..uri_offset + 1 + uri_len];
-            match std::str::from_utf8(uri_bytes) {
-                Ok(uri_str) => {
-                    msg!("URI to set: {}", uri_str);
-                    Some(uri_str.to_string())
-                },
-                Err(_) => {
-                    msg!("Error: Failed to parse uri from instruction data");
-                    None
-                }
-            }
-        } else {
-            None
+    if let Some(creators) = &creators {
+        for creator in creators {
+            msg!("Creator to set: {} ({}%)", creator.address, creator.share);
         }
-    } else {
-        None
-    };
-    
-    // Получаем sellerFeeBasisPoints
-    let fee_offset = if let Some(uri_str) = &uri {
-        uri_offset + 1 + uri_str.len()
-    } else {
-        uri_offset
-    };
-    
-    let seller_fee_basis_points = if has_metadata && instruction_data.len() >= fee_offset + 2 {
-        let fee = ((instruction_data[fee_offset] as u16) << 8) | (instruction_data[fee_offset + 1] as u16);
+    }
+    if let Some(name_str) = &name {
+        msg!("Name to set: {}", name_str);
+    }
+    if let Some(symbol_str) = &symbol {
+        msg!("Symbol to set: {}", symbol_str);
+    }
+    if let Some(uri_str) = &uri {
+        msg!("URI to set: {}", uri_str);
+    }
+    if let Some(fee) = seller_fee_basis_points {
         msg!("Seller fee basis points to set: {}", fee);
-        Some(fee)
-    } else {
-        None
-    };
+    }
+    if let Some(primary_sale) = primary_sale_happened {
+        msg!("Primary sale happened to set: {}", primary_sale);
+    }
+    if let Some(mutable) = is_mutable {
+        msg!("Is mutable to set: {}", mutable);
+    }
     
     let accounts_iter = &mut accounts.iter();
     
@@ -159,20 +76,12 @@ pub fn process_instruction(
     let system_program = next_account_info(accounts_iter)?;
     let sysvar_instructions = next_account_info(accounts_iter)?;
     let token_metadata_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
     
-    // Проверяем подписи
-    if !payer.is_signer {
-        msg!("Payer must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    // Проверяем, что mint_account соответствует переданному в инструкции
-    if mint_account.key != &mint_address {
-        msg!("Invalid mint address provided");
-        msg!("Expected: {}", mint_address);
-        msg!("Received: {}", mint_account.key);
-        return Err(ProgramError::InvalidArgument);
-    }
+    // Обновлять метаданные может только администратор (он же payer)
+    let program_config = ProgramConfig::assert_admin(program_config_account, payer, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_UPDATE)?;
+    assert_token_metadata_program(token_metadata_program)?;
 
     // Проверяем, что program_authority это правильный PDA
     let (expected_authority, bump_seed) = Pubkey::find_program_address(
@@ -181,7 +90,7 @@ pub fn process_instruction(
     );
     if program_authority.key != &expected_authority {
         msg!("Invalid program authority provided");
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidProgramAuthority.into());
     }
 
     // Создаем authority seeds для подписи
@@ -191,6 +100,109 @@ pub fn process_instruction(
     ];
     let signers = &[&authority_signature_seeds[..]];
 
+    update_metadata(
+        metadata_account,
+        mint_account,
+        program_authority,
+        payer,
+        system_program,
+        sysvar_instructions,
+        signers,
+        args,
+    )?;
+
+    msg!("NFT metadata updated successfully!");
+    Ok(())
+}
+
+// Обновляет метаданные одного NFT через UpdateV1, подписывая PDA программы.
+// Все проверки выполняются до CPI: при ошибке метаданные не меняются.
+// Используется инструкциями 43, 59 и 60
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_metadata<'a>(
+    metadata_account: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    program_authority: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    sysvar_instructions: &AccountInfo<'a>,
+    signers: &[&[&[u8]]],
+    args: UpdateMetadataArgsV2,
+) -> ProgramResult {
+    let UpdateMetadataArgsV2 {
+        mint: mint_address,
+        creators,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        primary_sale_happened,
+        is_mutable,
+    } = args;
+
+    // Проверяем, что mint_account соответствует переданному в инструкции
+    if mint_account.key != &mint_address {
+        msg!("Invalid mint address provided");
+        msg!("Expected: {}", mint_address);
+        msg!("Received: {}", mint_account.key);
+        return Err(RError::MintMismatch.into());
+    }
+
+    // Читаем текущие метаданные: незаданные поля берутся из них
+    let (expected_metadata, _) = Metadata::find_pda(mint_account.key);
+    if metadata_account.key != &expected_metadata || metadata_account.owner != &mpl_token_metadata::ID {
+        msg!("Invalid metadata account provided");
+        msg!("Expected: {}", expected_metadata);
+        msg!("Received: {}", metadata_account.key);
+        return Err(RError::InvalidMetadataAccount.into());
+    }
+    let current = Metadata::try_from(metadata_account).map_err(|err| {
+        msg!("Failed to deserialize metadata: {}", err);
+        RError::InvalidMetadataAccount
+    })?;
+    if !current.is_mutable {
+        msg!("Metadata is immutable");
+        return Err(RError::MetadataImmutable.into());
+    }
+
+    // Token Metadata хранит строки с нулевым дополнением
+    let current_name = current.name.trim_end_matches('\0').to_string();
+    let current_symbol = current.symbol.trim_end_matches('\0').to_string();
+    let current_uri = current.uri.trim_end_matches('\0').to_string();
+
+    let name = name.unwrap_or(current_name);
+    let symbol = symbol.unwrap_or(current_symbol);
+    let uri = uri.unwrap_or(current_uri);
+    let seller_fee_basis_points = seller_fee_basis_points.unwrap_or(current.seller_fee_basis_points);
+
+    // Проверяем ограничения Token Metadata на строки и комиссию
+    if name.len() > MAX_NAME_TEMPLATE_LENGTH {
+        msg!("Name is too long: {} > {}", name.len(), MAX_NAME_TEMPLATE_LENGTH);
+        return Err(RError::NameTooLong.into());
+    }
+    if symbol.len() > MAX_SYMBOL_LENGTH {
+        msg!("Symbol is too long: {} > {}", symbol.len(), MAX_SYMBOL_LENGTH);
+        return Err(RError::SymbolTooLong.into());
+    }
+    if uri.len() > MAX_URI_BASE_LENGTH {
+        msg!("URI is too long: {} > {}", uri.len(), MAX_URI_BASE_LENGTH);
+        return Err(RError::UriTooLong.into());
+    }
+    if seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+        msg!("Seller fee basis points must be <= {}", MAX_SELLER_FEE_BASIS_POINTS);
+        return Err(RError::InvalidSellerFeeBasisPoints.into());
+    }
+
+    // Новый список создателей сохраняет подписи уже верифицированных создателей
+    let creators = match creators {
+        Some(creators) => Some(build_creators(
+            &creators,
+            program_authority.key,
+            current.creators.as_deref().unwrap_or_default(),
+        )?),
+        None => current.creators,
+    };
+
     // Создаем структуру для обновления метаданных
     let update_v1 = UpdateV1 {
         authority: *program_authority.key,
@@ -207,10 +219,16 @@ pub fn process_instruction(
     };
 
     // Создаем аргументы для обновления
-    let mut update_args = UpdateV1InstructionArgs {
-        data: None,
-        is_mutable: Some(true),
-        primary_sale_happened: None,
+    let update_args = UpdateV1InstructionArgs {
+        data: Some(Data {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+        }),
+        primary_sale_happened,
+        is_mutable,
         new_update_authority: None,
         collection_details: CollectionDetailsToggle::None,
         uses: UsesToggle::None,
@@ -219,27 +237,6 @@ pub fn process_instruction(
         authorization_data: None,
     };
 
-    // Создаем данные для обновления
-    let mut creators_vec = Vec::new();
-    if let Some(creator_pubkey) = creator_address {
-        let creator = Creator {
-            address: creator_pubkey,
-            verified: false,
-            share: 100,
-        };
-        creators_vec.push(creator);
-    }
-
-    let data = Data {
-        name: name.unwrap_or_else(|| "".to_string()),
-        symbol: symbol.unwrap_or_else(|| "".to_string()),
-        uri: uri.unwrap_or_else(|| "".to_string()),
-        seller_fee_basis_points: seller_fee_basis_points.unwrap_or(0),
-        creators: if creators_vec.is_empty() { None } else { Some(creators_vec) },
-    };
-
-    update_args.data = Some(data);
-
     msg!("Invoking UpdateV1 instruction...");
     invoke_signed(
         &update_v1.instruction(update_args),
@@ -254,6 +251,5 @@ pub fn process_instruction(
         signers,
     )?;
 
-    msg!("NFT metadata updated successfully!");
     Ok(())
-} 
\ No newline at end of file
+}
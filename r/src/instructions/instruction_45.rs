@@ -1,4 +1,7 @@
-// pNFT с проверками Merkle и фиксированной коллекцией (стандартный SPL Token)
+// pNFT с проверками Merkle и коллекцией раунда
+//
+// Программа токенов (SPL Token или Token-2022), коллекция и шаблоны названия и URI
+// задаются в конфигурации раунда
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -9,52 +12,47 @@ use solana_program::{
     pubkey::Pubkey,
     msg,
     system_instruction,
+    sysvar::clock::Clock,
     sysvar::rent::Rent,
     sysvar::Sysvar,
-    hash::hash,
 };
 use mpl_token_metadata::{
     instructions::{CreateV1, CreateV1InstructionArgs, MintV1, MintV1InstructionArgs},
     types::{TokenStandard, PrintSupply, Collection},
 };
-use spl_token::ID as TOKEN_PROGRAM_ID;
-
-use crate::ALL_MERKLE_ROOTS;
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::{
+    extension::{
+        metadata_pointer,
+        transfer_fee::{instruction::transfer_checked_with_fee, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction::{burn_checked, initialize_mint2, transfer_checked},
+    state::Mint,
+};
 
-// Фиксированный адрес коллекции
-const COLLECTION_MINT: &str = "YAP8v1Y4aKiM6HEtpCDabXz7VgH9SXxUgTBHbWaN7hg";
+use crate::error::RError;
+use crate::instruction::MintWithProofArgsV1;
+use crate::merkle::verify_wallet_nft;
+use crate::state::{
+    assert_not_paused, assert_token_metadata_program, assert_treasury, ClaimBitmap, Key,
+    MerkleLeafVersion, MintRecordV2, ProgramConfig, RoundConfig, PAUSE_MINT,
+};
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    args: MintWithProofArgsV1,
 ) -> ProgramResult {
-    msg!("Starting create_and_mint_pnft_with_standard_token_merkle_proof_and_fixed_collection...");
+    msg!("Instruction 45: Mint with proof");
     
-    // Проверяем, что данные имеют правильный формат
-    if instruction_data.len() < 10 { // 8 байт для раунда + 2 байта для NFTnumber
-        msg!("Invalid proof data: missing round number or NFTnumber");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-    
-    // Получаем номер раунда из первого байта
-    let round_number = instruction_data[0] as u64;
+    let MintWithProofArgsV1 { round: round_number, nft_number, proof } = args;
     msg!("Using round number: {}", round_number);
-    
-    // Получаем NFTnumber из следующих 2 байт (uint16)
-    let nft_number = u16::from_le_bytes([instruction_data[1], instruction_data[2]]);
     msg!("Using NFTnumber: {}", nft_number);
     
-    // Проверяем, что номер раунда валидный
-    if (round_number as usize) >= ALL_MERKLE_ROOTS.len() {
-        msg!("Invalid round number: {}, max is {}", round_number, ALL_MERKLE_ROOTS.len() - 1);
-        return Err(ProgramError::InvalidArgument);
-    }
-    
-    // Получаем корень Merkle для указанного раунда
-    let merkle_root = ALL_MERKLE_ROOTS[round_number as usize];
-    msg!("Using Merkle root for round {}", round_number);
-    
     let accounts_iter = &mut accounts.iter();
     
     // Получаем все необходимые аккаунты
@@ -76,7 +74,15 @@ pub fn process_instruction(
     let collection_mint = next_account_info(accounts_iter)?;
     let collection_metadata = next_account_info(accounts_iter)?;
     let collection_master_edition = next_account_info(accounts_iter)?;
-    let collection_authority_record = next_account_info(accounts_iter)?;
+    let _collection_authority_record = next_account_info(accounts_iter)?;
+    let round_config_account = next_account_info(accounts_iter)?;
+    let claim_bitmap_account = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let treasury = next_account_info(accounts_iter)?;
+
+    // Минт можно поставить на паузу глобально (инструкция 54)
+    let program_config = ProgramConfig::load(program_config_account, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_MINT)?;
     
     // Проверяем подписи
     if !mint_account.is_signer {
@@ -88,30 +94,6 @@ pub fn process_instruction(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Проверяем, что token_program имеет правильный ID
-    if token_program.key != &TOKEN_PROGRAM_ID {
-        msg!("Invalid Token program ID provided");
-        msg!("Expected: {}", TOKEN_PROGRAM_ID);
-        msg!("Received: {}", token_program.key);
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    // Проверяем, что collection_mint имеет правильный адрес
-    let expected_collection_mint = match Pubkey::try_from(COLLECTION_MINT) {
-        Ok(pubkey) => pubkey,
-        Err(_) => {
-            msg!("Failed to parse collection mint address");
-            return Err(ProgramError::InvalidArgument);
-        }
-    };
-    
-    if collection_mint.key != &expected_collection_mint {
-        msg!("Invalid collection mint address provided");
-        msg!("Expected: {}", expected_collection_mint);
-        msg!("Received: {}", collection_mint.key);
-        return Err(ProgramError::InvalidArgument);
-    }
-
     // Проверяем, что program_authority это правильный PDA
     let (expected_authority, bump_seed) = Pubkey::find_program_address(
         &[b"mint_authority"],
@@ -119,42 +101,54 @@ pub fn process_instruction(
     );
     if program_authority.key != &expected_authority {
         msg!("Invalid program authority provided");
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidProgramAuthority.into());
     }
 
-    // Проверяем Merkle proof
-    msg!("Verifying Merkle proof...");
-    
-    // Проверяем, что данные доказательства имеют правильный формат
-    if (instruction_data.len() - 3) % 32 != 0 { // 3 байта для раунда и NFTnumber
-        msg!("Invalid proof data length");
-        return Err(ProgramError::InvalidInstructionData);
+    // Получаем корень Merkle из конфигурации раунда
+    let round_config = RoundConfig::load(round_config_account, round_number, program_id)?;
+
+    // Минт разрешен только в окне раунда и если раунд не на паузе
+    let clock = Clock::get()?;
+    round_config.assert_mint_window(clock.unix_timestamp)?;
+    assert_not_paused(round_config.paused, PAUSE_MINT)?;
+
+    // Коллекция раунда (или коллекция программы, если у раунда она не задана)
+    round_config.assert_collection_mint(&program_config, collection_mint)?;
+
+    // Проверяем, что token_program совпадает с программой токенов раунда
+    let expected_token_program = round_config.token_program_id();
+    if token_program.key != &expected_token_program {
+        msg!("Invalid Token program ID provided");
+        msg!("Expected: {}", expected_token_program);
+        msg!("Received: {}", token_program.key);
+        return Err(RError::InvalidTokenProgram.into());
     }
-    
-    // Преобразуем данные доказательства в вектор 32-байтных массивов
-    let mut proof = Vec::new();
-    for i in 0..((instruction_data.len() - 3) / 32) {
-        let mut node = [0u8; 32];
-        node.copy_from_slice(&instruction_data[3 + i * 32..3 + (i + 1) * 32]);
-        proof.push(node);
+
+    // Раунды с лимитом минта на кошелек учитываются записью лимита (инструкция 62)
+    if round_config.leaf_version == MerkleLeafVersion::Allowance {
+        msg!("Round {} uses allowance leaves, use instruction 62", round_number);
+        return Err(RError::UnsupportedLeafVersion.into());
     }
+
+    let merkle_root = round_config.merkle_root;
+    msg!("Using Merkle root for round {} ({:?} leaves)", round_number, round_config.leaf_version);
+
+    // Проверяем Merkle proof
+    msg!("Verifying Merkle proof...");
     
-    // Создаем буфер для NFTnumber (2 байта)
-    let mut nft_number_bytes = [0u8; 2];
-    nft_number_bytes.copy_from_slice(&[instruction_data[1], instruction_data[2]]);
-    
-    // Вычисляем хеш (лист) для адреса плательщика с учетом NFTnumber
-    let mut combined_data = Vec::with_capacity(34); // 32 байта для адреса + 2 байта для NFTnumber
-    combined_data.extend_from_slice(payer.key.as_ref());
-    combined_data.extend_from_slice(&nft_number_bytes);
-    
-    let leaf = hash(&combined_data).to_bytes();
-    
-    // Проверяем доказательство
-    if !verify_merkle_proof(leaf, &proof, merkle_root) {
+    // Лист для адреса плательщика с учетом NFTnumber в формате раунда
+    if !verify_wallet_nft(
+        round_config.leaf_version,
+        program_id,
+        round_number,
+        payer.key,
+        nft_number,
+        &proof,
+        merkle_root,
+    ) {
         msg!("Invalid Merkle proof for address: {} with NFTnumber: {} in round {}", 
              payer.key, nft_number, round_number);
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::NotOnAllowlist.into());
     }
     
     msg!("Merkle proof verified successfully for round {} with NFTnumber {}!", round_number, nft_number);
@@ -171,48 +165,35 @@ pub fn process_instruction(
     
     if mint_record_account.key != &expected_mint_record_address {
         msg!("Invalid mint record account address");
-        return Err(ProgramError::InvalidArgument);
+        return Err(RError::InvalidMintRecordAddress.into());
     }
     
     if !mint_record_account.data_is_empty() && mint_record_account.owner == program_id {
         msg!("User has already minted in round {}", round_number);
-        return Err(ProgramError::AccountAlreadyInitialized);
+        return Err(RError::AlreadyMinted.into());
     }
 
-    // Проверяем, что collection_metadata и collection_master_edition соответствуют collection_mint
-    let (expected_collection_metadata, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            token_metadata_program.key.as_ref(),
-            collection_mint.key.as_ref(),
-        ],
-        token_metadata_program.key
-    );
-    
-    if collection_metadata.key != &expected_collection_metadata {
-        msg!("Invalid collection metadata address");
-        msg!("Expected: {}", expected_collection_metadata);
-        msg!("Received: {}", collection_metadata.key);
-        return Err(ProgramError::InvalidArgument);
-    }
-    
-    let (expected_collection_master_edition, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            token_metadata_program.key.as_ref(),
-            collection_mint.key.as_ref(),
-            b"edition",
-        ],
-        token_metadata_program.key
-    );
-    
-    if collection_master_edition.key != &expected_collection_master_edition {
-        msg!("Invalid collection master edition address");
-        msg!("Expected: {}", expected_collection_master_edition);
-        msg!("Received: {}", collection_master_edition.key);
-        return Err(ProgramError::InvalidArgument);
+    // Если для раунда создана битовая карта, минт отмечается в ней по NFTnumber
+    let claim_bitmap = ClaimBitmap::load(claim_bitmap_account, round_number, program_id)?;
+    if let Some(bitmap) = &claim_bitmap {
+        if bitmap.is_claimed(claim_bitmap_account, nft_number)? {
+            msg!("NFTnumber {} has already been minted in round {}", nft_number, round_number);
+            return Err(RError::NftNumberAlreadyClaimed.into());
+        }
     }
 
+    // Проверяем, что collection_metadata и collection_master_edition соответствуют collection_mint
+    assert_collection_accounts(
+        token_metadata_program,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    )?;
+
+    // Оплата минта поступает в казну (вывод только администратором, возвратов нет)
+    assert_treasury(treasury, program_id)?;
+    pay_mint_price(&round_config, 1, payer, treasury, system_program, spl_ata_program, accounts_iter)?;
+
     // Создаем authority seeds для подписи
     let authority_signature_seeds = &[
         b"mint_authority".as_ref(),
@@ -220,43 +201,164 @@ pub fn process_instruction(
     ];
     let signers = &[&authority_signature_seeds[..]];
 
-    // Создаем минт аккаунт с использованием стандартного SPL Token
-    msg!("Creating mint account with standard SPL Token...");
     let rent = Rent::get()?;
-    let mint_len = 82; // Размер аккаунта Mint в байтах
-    let lamports = rent.minimum_balance(mint_len);
 
-    invoke(
-        &system_instruction::create_account(
-            &payer.key,
-            &mint_account.key,
-            lamports,
-            mint_len as u64,
-            &TOKEN_PROGRAM_ID,
-        ),
-        &[
-            payer.clone(),
-            mint_account.clone(),
-            system_program.clone(),
-        ],
-    )?;
+    if let Some(bitmap) = &claim_bitmap {
+        // Отмечаем NFTnumber в битовой карте раунда вместо отдельной записи о минте
+        msg!("Marking NFTnumber {} in claim bitmap...", nft_number);
+        bitmap.set_claimed(claim_bitmap_account, nft_number)?;
+    } else {
+        // Создаем PDA для расширенного отслеживания минтинга
+        msg!("Creating extended mint record PDA...");
+        let mint_record_size = MintRecordV2::LEN;
+        let mint_record_lamports = rent.minimum_balance(mint_record_size);
+    
+        let mint_record_signature_seeds = &[
+            b"minted".as_ref(),
+            &round_number.to_le_bytes(),
+            payer.key.as_ref(),
+            &[mint_record_bump],
+        ];
+        let mint_record_signers = &[&mint_record_signature_seeds[..]];
+    
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                &expected_mint_record_address,
+                mint_record_lamports,
+                mint_record_size as u64,
+                program_id,
+            ),
+            &[
+                payer.clone(),
+                mint_record_account.clone(),
+                system_program.clone(),
+            ],
+            mint_record_signers,
+        )?;
+    
+        MintRecordV2 {
+            key: Key::MintRecordV2,
+            version: MintRecordV2::VERSION,
+            round: round_number,
+            mint: *mint_account.key,
+            nft_number,
+            minter: *payer.key,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            bump: mint_record_bump,
+        }
+        .save(mint_record_account)?;
+    }
 
-    // Инициализируем минт с использованием стандартного SPL Token
-    msg!("Initializing mint with standard SPL Token...");
-    let init_mint_ix = spl_token::instruction::initialize_mint(
-        &TOKEN_PROGRAM_ID,
-        &mint_account.key,
-        &program_authority.key,
-        Some(&program_authority.key),
-        0,
+    // Создаем минт, метаданные с коллекцией, минтим токен и верифицируем коллекцию
+    mint_round_nft(
+        &RoundMintAccounts {
+            program_authority,
+            payer,
+            system_program,
+            sysvar_instructions,
+            token_program,
+            token_owner,
+            spl_ata_program,
+            token_metadata_program,
+            rent_sysvar,
+            collection_mint,
+            collection_metadata,
+            collection_master_edition,
+        },
+        &NftAccounts {
+            metadata: metadata_account,
+            master_edition: master_edition_account,
+            mint: mint_account,
+            token_account,
+            token_record,
+        },
+        &round_config,
+        nft_number,
+        signers,
+        &rent,
     )?;
-    
-    invoke(
-        &init_mint_ix,
-        &[
-            mint_account.clone(),
-            rent_sysvar.clone(),
-        ],
+
+    msg!("pNFT created, minted and added to collection successfully with Merkle proof verification!");
+    Ok(())
+}
+
+// Аккаунты, общие для минта NFT раунда (инструкции 45, 61 и 62)
+pub(crate) struct RoundMintAccounts<'a, 'b> {
+    pub program_authority: &'b AccountInfo<'a>,
+    pub payer: &'b AccountInfo<'a>,
+    pub system_program: &'b AccountInfo<'a>,
+    pub sysvar_instructions: &'b AccountInfo<'a>,
+    pub token_program: &'b AccountInfo<'a>,
+    pub token_owner: &'b AccountInfo<'a>,
+    pub spl_ata_program: &'b AccountInfo<'a>,
+    pub token_metadata_program: &'b AccountInfo<'a>,
+    pub rent_sysvar: &'b AccountInfo<'a>,
+    pub collection_mint: &'b AccountInfo<'a>,
+    pub collection_metadata: &'b AccountInfo<'a>,
+    pub collection_master_edition: &'b AccountInfo<'a>,
+}
+
+// Аккаунты одного NFT
+pub(crate) struct NftAccounts<'a, 'b> {
+    pub metadata: &'b AccountInfo<'a>,
+    pub master_edition: &'b AccountInfo<'a>,
+    pub mint: &'b AccountInfo<'a>,
+    pub token_account: &'b AccountInfo<'a>,
+    pub token_record: &'b AccountInfo<'a>,
+}
+
+// Создает pNFT раунда с номером nft_number: минт, метаданные и master edition
+// с коллекцией раунда, токен владельцу и верификация коллекции
+pub(crate) fn mint_round_nft<'a>(
+    accounts: &RoundMintAccounts<'a, '_>,
+    nft: &NftAccounts<'a, '_>,
+    round_config: &RoundConfig,
+    nft_number: u16,
+    signers: &[&[&[u8]]],
+    rent: &Rent,
+) -> ProgramResult {
+    let RoundMintAccounts {
+        program_authority,
+        payer,
+        system_program,
+        sysvar_instructions,
+        token_program,
+        token_owner,
+        spl_ata_program,
+        token_metadata_program,
+        rent_sysvar,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    } = *accounts;
+    let NftAccounts {
+        metadata: metadata_account,
+        master_edition: master_edition_account,
+        mint: mint_account,
+        token_account,
+        token_record,
+    } = *nft;
+
+    // Merkle-доказательство подтверждает права плательщика: NFT получает только он
+    if token_owner.key != payer.key {
+        msg!("Token owner must be the payer");
+        msg!("Expected: {}", payer.key);
+        msg!("Received: {}", token_owner.key);
+        return Err(RError::InvalidTokenOwner.into());
+    }
+    assert_token_metadata_program(token_metadata_program)?;
+
+    // Создаем и инициализируем минт NFT программой токенов раунда
+    create_nft_mint(
+        payer,
+        mint_account,
+        metadata_account,
+        program_authority,
+        token_program,
+        system_program,
+        rent,
     )?;
 
     // Создаем метаданные и master edition с указанием коллекции
@@ -270,7 +372,7 @@ pub fn process_instruction(
         update_authority: (*program_authority.key, true),
         system_program: *system_program.key,
         sysvar_instructions: *sysvar_instructions.key,
-        spl_token_program: Some(*token_program.key), // Используем стандартный SPL Token
+        spl_token_program: Some(*token_program.key),
     };
 
     // Создаем структуру Collection для указания коллекции
@@ -280,10 +382,10 @@ pub fn process_instruction(
     };
 
     let create_args = CreateV1InstructionArgs {
-        name: format!("Yapster Dimensions #{}", nft_number),
-        symbol: "YAPDIM".to_string(),
-        uri: "https://a.b/c.json".to_string(),
-        seller_fee_basis_points: 1000,
+        name: round_config.nft_name(nft_number),
+        symbol: round_config.symbol.clone(),
+        uri: round_config.nft_uri(nft_number),
+        seller_fee_basis_points: round_config.seller_fee_basis_points,
         creators: Some(vec![
             mpl_token_metadata::types::Creator {
                 address: *program_authority.key,
@@ -318,14 +420,14 @@ pub fn process_instruction(
         signers,
     )?;
 
-    // Создаем ассоциированный токен аккаунт с использованием стандартного SPL Token
-    msg!("Creating associated token account with standard SPL Token...");
+    // Создаем ассоциированный токен аккаунт
+    msg!("Creating associated token account...");
     invoke(
         &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
             payer.key,
             token_owner.key,
             mint_account.key,
-            &TOKEN_PROGRAM_ID,
+            token_program.key,
         ),
         &[
             payer.clone(),
@@ -339,40 +441,8 @@ pub fn process_instruction(
         ],
     )?;
 
-    // Создаем PDA для расширенного отслеживания минтинга
-    msg!("Creating extended mint record PDA for standard SPL Token...");
-    let mint_record_size = 32;
-    let mint_record_lamports = rent.minimum_balance(mint_record_size);
-    
-    let mint_record_signature_seeds = &[
-        b"minted".as_ref(),
-        &round_number.to_le_bytes(),
-        payer.key.as_ref(),
-        &[mint_record_bump],
-    ];
-    let mint_record_signers = &[&mint_record_signature_seeds[..]];
-    
-    invoke_signed(
-        &system_instruction::create_account(
-            &payer.key,
-            &expected_mint_record_address,
-            mint_record_lamports,
-            mint_record_size as u64,
-            program_id,
-        ),
-        &[
-            payer.clone(),
-            mint_record_account.clone(),
-            system_program.clone(),
-        ],
-        mint_record_signers,
-    )?;
-    
-    let mut data = mint_record_account.try_borrow_mut_data()?;
-    data[0..32].copy_from_slice(&mint_account.key.to_bytes());
-
-    // Минтим токен с использованием стандартного SPL Token
-    msg!("Minting token with standard SPL Token...");
+    // Минтим токен
+    msg!("Minting token with {}...", token_program.key);
     let mint_v1 = MintV1 {
         token: *token_account.key,
         token_owner: Some(*token_owner.key),
@@ -384,7 +454,7 @@ pub fn process_instruction(
         payer: *payer.key,
         system_program: *system_program.key,
         sysvar_instructions: *sysvar_instructions.key,
-        spl_token_program: *token_program.key, // Используем стандартный SPL Token
+        spl_token_program: *token_program.key,
         spl_ata_program: *spl_ata_program.key,
         authorization_rules_program: None,
         authorization_rules: None,
@@ -443,19 +513,309 @@ pub fn process_instruction(
         signers,
     )?;
 
-    msg!("pNFT created, minted and added to collection successfully with standard SPL Token and Merkle proof verification!");
     Ok(())
 }
 
-// Вспомогательная функция для проверки меркл доказательства
-fn verify_merkle_proof(leaf: [u8;32], proof: &Vec<[u8;32]>, root: [u8;32]) -> bool {
-    let mut computed = leaf;
-    for node in proof.iter() {
-        let (min, max) = if computed <= *node { (computed, *node) } else { (*node, computed) };
-        let mut bytes = [0u8;64];
-        bytes[..32].copy_from_slice(&min);
-        bytes[32..].copy_from_slice(&max);
-        computed = hash(&bytes).to_bytes();
+// Проверяет, что collection_metadata и collection_master_edition - PDA Token Metadata для collection_mint
+pub(crate) fn assert_collection_accounts(
+    token_metadata_program: &AccountInfo,
+    collection_mint: &AccountInfo,
+    collection_metadata: &AccountInfo,
+    collection_master_edition: &AccountInfo,
+) -> ProgramResult {
+    let (expected_collection_metadata, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.key.as_ref(),
+        ],
+        token_metadata_program.key
+    );
+    
+    if collection_metadata.key != &expected_collection_metadata {
+        msg!("Invalid collection metadata address");
+        msg!("Expected: {}", expected_collection_metadata);
+        msg!("Received: {}", collection_metadata.key);
+        return Err(RError::InvalidCollectionMetadata.into());
+    }
+    
+    let (expected_collection_master_edition, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.key.as_ref(),
+            b"edition",
+        ],
+        token_metadata_program.key
+    );
+    
+    if collection_master_edition.key != &expected_collection_master_edition {
+        msg!("Invalid collection master edition address");
+        msg!("Expected: {}", expected_collection_master_edition);
+        msg!("Received: {}", collection_master_edition.key);
+        return Err(RError::InvalidCollectionMasterEdition.into());
+    }
+
+    Ok(())
+}
+
+// Создает минт NFT нужного размера: для Token-2022 с расширением MetadataPointer,
+// указывающим на аккаунт метаданных Token Metadata
+pub(crate) fn create_nft_mint<'a>(
+    payer: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    metadata_account: &AccountInfo<'a>,
+    program_authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &Rent,
+) -> ProgramResult {
+    let is_token_2022 = token_program.key == &spl_token_2022::ID;
+    let extensions: &[ExtensionType] = if is_token_2022 {
+        &[ExtensionType::MetadataPointer]
+    } else {
+        &[]
+    };
+    let mint_len = ExtensionType::try_calculate_account_len::<Mint>(extensions)?;
+    msg!("Creating mint account ({} bytes) with {}...", mint_len, token_program.key);
+
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            mint_account.key,
+            rent.minimum_balance(mint_len),
+            mint_len as u64,
+            token_program.key,
+        ),
+        &[
+            payer.clone(),
+            mint_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    // Расширения Token-2022 инициализируются до самого минта
+    if is_token_2022 {
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                token_program.key,
+                mint_account.key,
+                Some(*program_authority.key),
+                Some(*metadata_account.key),
+            )?,
+            std::slice::from_ref(mint_account),
+        )?;
+    }
+
+    msg!("Initializing mint...");
+    invoke(
+        &initialize_mint2(
+            token_program.key,
+            mint_account.key,
+            program_authority.key,
+            Some(program_authority.key),
+            0,
+        )?,
+        std::slice::from_ref(mint_account),
+    )
+}
+
+// Оплата минта quantity NFT в казну: лампорты и токен по ценам раунда. Аккаунты
+// оплаты токеном берутся из accounts_iter, только если у раунда задана цена в токене
+pub(crate) fn pay_mint_price<'a: 'b, 'b>(
+    round_config: &RoundConfig,
+    quantity: u64,
+    payer: &AccountInfo<'a>,
+    treasury: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    spl_ata_program: &AccountInfo<'a>,
+    accounts_iter: &mut impl Iterator<Item = &'b AccountInfo<'a>>,
+) -> ProgramResult {
+    if round_config.price_lamports > 0 {
+        let price = round_config
+            .price_lamports
+            .checked_mul(quantity)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        msg!("Paying mint price: {} lamports", price);
+        invoke(
+            &system_instruction::transfer(payer.key, treasury.key, price),
+            &[
+                payer.clone(),
+                treasury.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    if round_config.price_amount > 0 {
+        let payment_mint = next_account_info(accounts_iter)?;
+        let payment_token_account = next_account_info(accounts_iter)?;
+        let payment_vault = next_account_info(accounts_iter)?;
+        let payment_token_program = next_account_info(accounts_iter)?;
+        pay_with_token(
+            round_config,
+            quantity,
+            payer,
+            treasury,
+            payment_mint,
+            payment_token_account,
+            payment_vault,
+            payment_token_program,
+            system_program,
+            spl_ata_program,
+        )?;
+    }
+    Ok(())
+}
+
+// Оплата минта quantity NFT токеном: сжигаем burn_basis_points от цены со счета
+// плательщика, остаток переводим в хранилище (ATA казны). Комиссию за перевод
+// минта Token-2022 (TransferFee) плательщик доплачивает сверх цены, чтобы
+// хранилище получило свою долю полностью
+#[allow(clippy::too_many_arguments)]
+fn pay_with_token<'a>(
+    round_config: &RoundConfig,
+    quantity: u64,
+    payer: &AccountInfo<'a>,
+    treasury: &AccountInfo<'a>,
+    payment_mint: &AccountInfo<'a>,
+    payment_token_account: &AccountInfo<'a>,
+    payment_vault: &AccountInfo<'a>,
+    payment_token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    spl_ata_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if payment_mint.key != &round_config.price_mint {
+        msg!("Invalid payment mint provided");
+        msg!("Expected: {}", round_config.price_mint);
+        msg!("Received: {}", payment_mint.key);
+        return Err(RError::InvalidPaymentMint.into());
+    }
+
+    if payment_token_program.key != &spl_token::ID && payment_token_program.key != &spl_token_2022::ID {
+        msg!("Payment token program must be SPL Token or Token-2022");
+        return Err(RError::InvalidTokenProgram.into());
+    }
+    if payment_mint.owner != payment_token_program.key {
+        msg!("Payment mint does not belong to the payment token program");
+        return Err(RError::InvalidPaymentMint.into());
+    }
+
+    let expected_vault = get_associated_token_address_with_program_id(
+        treasury.key,
+        payment_mint.key,
+        payment_token_program.key,
+    );
+    if payment_vault.key != &expected_vault {
+        msg!("Invalid payment vault provided");
+        msg!("Expected: {}", expected_vault);
+        msg!("Received: {}", payment_vault.key);
+        return Err(RError::InvalidPaymentVault.into());
     }
-    computed == root
-}
\ No newline at end of file
+
+    // Разбор минта подходит и для SPL Token, и для Token-2022 (с расширениями)
+    let (decimals, transfer_fee) = {
+        let mint_data = payment_mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let transfer_fee = match mint_state.get_extension::<TransferFeeConfig>() {
+            Ok(config) => Some(*config.get_epoch_fee(Clock::get()?.epoch)),
+            Err(_) => None,
+        };
+        (mint_state.base.decimals, transfer_fee)
+    };
+
+    let price = round_config
+        .price_amount
+        .checked_mul(quantity)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let burn_amount = (price as u128 * round_config.burn_basis_points as u128 / 10000) as u64;
+    let transfer_amount = price - burn_amount;
+    msg!("Paying mint price: {} tokens ({} burned, {} to vault)", price, burn_amount, transfer_amount);
+
+    if burn_amount > 0 {
+        invoke(
+            &burn_checked(
+                payment_token_program.key,
+                payment_token_account.key,
+                payment_mint.key,
+                payer.key,
+                &[],
+                burn_amount,
+                decimals,
+            )?,
+            &[
+                payment_token_account.clone(),
+                payment_mint.clone(),
+                payer.clone(),
+                payment_token_program.clone(),
+            ],
+        )?;
+    }
+
+    if transfer_amount > 0 {
+        // Хранилище создается при первой оплате
+        invoke(
+            &create_associated_token_account_idempotent(
+                payer.key,
+                treasury.key,
+                payment_mint.key,
+                payment_token_program.key,
+            ),
+            &[
+                payer.clone(),
+                payment_vault.clone(),
+                treasury.clone(),
+                payment_mint.clone(),
+                system_program.clone(),
+                payment_token_program.clone(),
+                spl_ata_program.clone(),
+            ],
+        )?;
+
+        let transfer = match transfer_fee {
+            // Переводим сумму с комиссией: хранилище получает transfer_amount
+            Some(transfer_fee) => {
+                let gross_amount = transfer_fee
+                    .calculate_pre_fee_amount(transfer_amount)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                let fee = transfer_fee
+                    .calculate_fee(gross_amount)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                msg!("Payment mint transfer fee: {} tokens on top of the price", fee);
+                transfer_checked_with_fee(
+                    payment_token_program.key,
+                    payment_token_account.key,
+                    payment_mint.key,
+                    payment_vault.key,
+                    payer.key,
+                    &[],
+                    gross_amount,
+                    decimals,
+                    fee,
+                )?
+            }
+            None => transfer_checked(
+                payment_token_program.key,
+                payment_token_account.key,
+                payment_mint.key,
+                payment_vault.key,
+                payer.key,
+                &[],
+                transfer_amount,
+                decimals,
+            )?,
+        };
+        invoke(
+            &transfer,
+            &[
+                payment_token_account.clone(),
+                payment_mint.clone(),
+                payment_vault.clone(),
+                payer.clone(),
+                payment_token_program.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// Инструкция 47: Создание конфигурации раунда (PDA с корнем Merkle и параметрами минта)
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::{RoundConfigArgs, RoundConfigArgsV2};
+use crate::state::{ProgramConfig, RoundConfig, ROUND_CONFIG_SEED};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RoundConfigArgs,
+) -> ProgramResult {
+    msg!("Instruction 47: Create round config");
+    // Параметры, которых нет в V1, получают значения по умолчанию
+    let args = args.into_v2(RoundConfigArgsV2::default());
+    msg!("Round: {}", args.round);
+
+    let accounts_iter = &mut accounts.iter();
+
+    let round_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+
+    // Создавать конфигурации раундов может только администратор
+    ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    // Проверяем адрес PDA конфигурации раунда
+    let (expected_round_config_address, bump_seed) = RoundConfig::find_address(args.round, program_id);
+    if round_config_account.key != &expected_round_config_address {
+        msg!("Invalid round config account address");
+        msg!("Expected: {}", expected_round_config_address);
+        msg!("Received: {}", round_config_account.key);
+        return Err(RError::InvalidRoundConfigAddress.into());
+    }
+
+    if !round_config_account.data_is_empty() {
+        msg!("Round config for round {} already exists", args.round);
+        return Err(RError::RoundConfigAlreadyExists.into());
+    }
+
+    let round = args.round;
+    let config = args.into_config(bump_seed);
+    config.validate()?;
+
+    // Создаем PDA конфигурации раунда
+    msg!("Creating round config PDA...");
+    let rent = Rent::get()?;
+    let round_config_lamports = rent.minimum_balance(RoundConfig::LEN);
+
+    let round_config_signature_seeds = &[
+        ROUND_CONFIG_SEED,
+        &round.to_le_bytes(),
+        &[bump_seed],
+    ];
+    let round_config_signers = &[&round_config_signature_seeds[..]];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            admin.key,
+            round_config_account.key,
+            round_config_lamports,
+            RoundConfig::LEN as u64,
+            program_id,
+        ),
+        &[
+            admin.clone(),
+            round_config_account.clone(),
+            system_program.clone(),
+        ],
+        round_config_signers,
+    )?;
+
+    config.save(round_config_account)?;
+
+    msg!("Round config for round {} created successfully!", round);
+    Ok(())
+}
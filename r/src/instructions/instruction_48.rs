@@ -0,0 +1,86 @@
+// Инструкция 48: Обновление конфигурации раунда
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    msg,
+    program::invoke,
+    system_instruction,
+    sysvar::clock::Clock,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::{RoundConfigArgs, RoundConfigArgsV2};
+use crate::state::{ProgramConfig, RoundConfig};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RoundConfigArgs,
+) -> ProgramResult {
+    msg!("Instruction 48: Update round config");
+    let round = args.round();
+    msg!("Round: {}", round);
+
+    let accounts_iter = &mut accounts.iter();
+
+    let round_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Обновлять конфигурации раундов может только администратор
+    ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    // Загружаем текущую конфигурацию (проверка владельца, PDA и дискриминатора)
+    let current = RoundConfig::load(round_config_account, round, program_id)?;
+    // Параметры, которых нет в V1, остаются как в текущей конфигурации
+    let args = args.into_v2(RoundConfigArgsV2::from(&current));
+
+    // bump и пауза раунда сохраняются (пауза меняется инструкциями 54/55)
+    let mut config = args.into_config(current.bump);
+    config.paused = current.paused;
+
+    // После начала раунда его начало не меняется: иначе администратор мог бы
+    // отодвинуть начало уже после минтов и задать корень раскрытия задним числом
+    let started = current.has_started(Clock::get()?.unix_timestamp);
+    if started && config.start_timestamp != current.start_timestamp {
+        msg!("Start timestamp of round {} cannot change after the round has started", round);
+        return Err(RError::RoundAlreadyStarted.into());
+    }
+
+    // Корень раскрытия - обязательство перед минтом: задается один раз и только
+    // до начала окна минта, иначе администратор мог бы подобрать редкость
+    if config.reveal_root != current.reveal_root && (current.reveal_root != [0u8; 32] || started) {
+        msg!("Reveal root can only be set once before the round starts");
+        return Err(RError::RevealRootLocked.into());
+    }
+    config.validate()?;
+
+    // Конфигурации, созданные до добавления новых полей, короче LEN: load читает
+    // их с нулевым хвостом, а для записи доплачиваем ренту и увеличиваем аккаунт
+    if round_config_account.data_len() < RoundConfig::LEN {
+        let required_lamports = Rent::get()?.minimum_balance(RoundConfig::LEN);
+        let current_lamports = round_config_account.lamports();
+        if current_lamports < required_lamports {
+            let top_up = required_lamports - current_lamports;
+            msg!("Topping up round config rent: {} lamports", top_up);
+            invoke(
+                &system_instruction::transfer(admin.key, round_config_account.key, top_up),
+                &[
+                    admin.clone(),
+                    round_config_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        round_config_account.realloc(RoundConfig::LEN, false)?;
+    }
+    config.save(round_config_account)?;
+
+    msg!("Round config for round {} updated successfully!", round);
+    Ok(())
+}
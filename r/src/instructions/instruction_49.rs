@@ -0,0 +1,132 @@
+// Инструкция 49: Инициализация конфигурации программы (назначение администратора)
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::InitializeProgramConfigArgsV1;
+use crate::state::{Key, ProgramConfig, PROGRAM_CONFIG_SEED};
+
+// Смещение upgrade authority в аккаунте ProgramData:
+// 4 байта тег UpgradeableLoaderState + 8 байт slot + 1 байт тег Option
+const PROGRAM_DATA_AUTHORITY_OFFSET: usize = 13;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: InitializeProgramConfigArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 49: Initialize program config");
+
+    let admin = args.admin;
+    msg!("Admin: {}", admin);
+
+    let accounts_iter = &mut accounts.iter();
+
+    let program_config_account = next_account_info(accounts_iter)?;
+    let upgrade_authority = next_account_info(accounts_iter)?;
+    let program_data = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Проверяем подпись upgrade authority
+    if !upgrade_authority.is_signer {
+        msg!("Upgrade authority must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Проверяем, что program_data - это аккаунт ProgramData нашей программы
+    let (expected_program_data, _) = Pubkey::find_program_address(
+        &[program_id.as_ref()],
+        &bpf_loader_upgradeable::id()
+    );
+    if program_data.key != &expected_program_data {
+        msg!("Invalid program data account");
+        msg!("Expected: {}", expected_program_data);
+        msg!("Received: {}", program_data.key);
+        return Err(RError::InvalidProgramData.into());
+    }
+
+    // Только upgrade authority программы может назначить первого администратора
+    {
+        let data = program_data.try_borrow_data()?;
+        if data.len() < PROGRAM_DATA_AUTHORITY_OFFSET + 32
+            || data[PROGRAM_DATA_AUTHORITY_OFFSET - 1] != 1
+        {
+            msg!("Program has no upgrade authority");
+            return Err(RError::NotUpgradeAuthority.into());
+        }
+        let authority = Pubkey::new_from_array(
+            data[PROGRAM_DATA_AUTHORITY_OFFSET..PROGRAM_DATA_AUTHORITY_OFFSET + 32].try_into().unwrap()
+        );
+        if upgrade_authority.key != &authority {
+            msg!("Signer is not the program upgrade authority");
+            msg!("Expected: {}", authority);
+            msg!("Received: {}", upgrade_authority.key);
+            return Err(RError::NotUpgradeAuthority.into());
+        }
+    }
+
+    // Проверяем адрес PDA конфигурации программы
+    let (expected_program_config_address, bump_seed) = ProgramConfig::find_address(program_id);
+    if program_config_account.key != &expected_program_config_address {
+        msg!("Invalid program config account address");
+        msg!("Expected: {}", expected_program_config_address);
+        msg!("Received: {}", program_config_account.key);
+        return Err(RError::InvalidProgramConfigAddress.into());
+    }
+
+    if !program_config_account.data_is_empty() {
+        msg!("Program config already initialized");
+        return Err(RError::ProgramConfigAlreadyInitialized.into());
+    }
+
+    // Создаем PDA конфигурации программы
+    msg!("Creating program config PDA...");
+    let rent = Rent::get()?;
+    let program_config_lamports = rent.minimum_balance(ProgramConfig::LEN);
+
+    let program_config_signature_seeds = &[
+        PROGRAM_CONFIG_SEED,
+        &[bump_seed],
+    ];
+    let program_config_signers = &[&program_config_signature_seeds[..]];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            upgrade_authority.key,
+            program_config_account.key,
+            program_config_lamports,
+            ProgramConfig::LEN as u64,
+            program_id,
+        ),
+        &[
+            upgrade_authority.clone(),
+            program_config_account.clone(),
+            system_program.clone(),
+        ],
+        program_config_signers,
+    )?;
+
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        admin,
+        pending_admin: None,
+        bump: bump_seed,
+        paused: 0,
+        collection_mint: Pubkey::default(),
+    };
+    config.save(program_config_account)?;
+
+    msg!("Program config initialized with admin {}", admin);
+    Ok(())
+}
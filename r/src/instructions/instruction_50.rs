@@ -0,0 +1,40 @@
+// Инструкция 50: Предложение нового администратора (первый шаг передачи прав)
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    msg,
+};
+
+use crate::instruction::ProposeAdminArgsV1;
+use crate::state::ProgramConfig;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ProposeAdminArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 50: Propose new admin");
+
+    let new_admin = args.new_admin;
+
+    let accounts_iter = &mut accounts.iter();
+
+    let program_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+
+    let mut config = ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    // Предложение текущего администратора отменяет передачу прав
+    if new_admin == config.admin {
+        msg!("Pending admin cleared");
+        config.pending_admin = None;
+    } else {
+        msg!("Pending admin: {}", new_admin);
+        config.pending_admin = Some(new_admin);
+    }
+    config.save(program_config_account)?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// Инструкция 51: Принятие прав администратора (второй шаг передачи прав)
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+};
+
+use crate::error::RError;
+use crate::state::ProgramConfig;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Instruction 51: Accept admin");
+
+    let accounts_iter = &mut accounts.iter();
+
+    let program_config_account = next_account_info(accounts_iter)?;
+    let new_admin = next_account_info(accounts_iter)?;
+
+    if !new_admin.is_signer {
+        msg!("New admin must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut config = ProgramConfig::load(program_config_account, program_id)?;
+
+    match config.pending_admin {
+        Some(pending_admin) if &pending_admin == new_admin.key => {}
+        Some(pending_admin) => {
+            msg!("Signer is not the pending admin");
+            msg!("Expected: {}", pending_admin);
+            msg!("Received: {}", new_admin.key);
+            return Err(RError::NotPendingAdmin.into());
+        }
+        None => {
+            msg!("No pending admin to accept");
+            return Err(RError::NoPendingAdmin.into());
+        }
+    }
+
+    msg!("Admin changed from {} to {}", config.admin, new_admin.key);
+    config.admin = *new_admin.key;
+    config.pending_admin = None;
+    config.save(program_config_account)?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// Инструкция 52: Перевод старой 32-байтной записи о минте в формат MintRecordV2
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::MigrateMintRecordArgsV1;
+use crate::state::{Key, MintRecordV1, MintRecordV2, ProgramConfig};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MigrateMintRecordArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 52: Migrate mint record to V2");
+    msg!("Round: {}", args.round);
+    msg!("NFTnumber: {}", args.nft_number);
+
+    let accounts_iter = &mut accounts.iter();
+
+    let mint_record_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let wallet = next_account_info(accounts_iter)?;
+
+    // Номер NFT для старых записей знает только администратор (он же доплачивает ренту)
+    ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+    msg!("Wallet: {}", wallet.key);
+
+    let (expected_mint_record_address, bump) =
+        MintRecordV2::find_address(args.round, wallet.key, program_id);
+    if mint_record_account.key != &expected_mint_record_address {
+        msg!("Invalid mint record account address");
+        msg!("Expected: {}", expected_mint_record_address);
+        msg!("Received: {}", mint_record_account.key);
+        return Err(RError::InvalidMintRecordAddress.into());
+    }
+
+    if mint_record_account.owner != program_id {
+        msg!("Mint record account does not belong to the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Читаем старую запись: ровно 32 байта с адресом минта
+    let data_len = mint_record_account.data_len();
+    if data_len == MintRecordV2::LEN
+        && mint_record_account.try_borrow_data()?[0] == Key::MintRecordV2 as u8
+    {
+        msg!("Mint record is already migrated");
+        return Err(RError::MintRecordAlreadyMigrated.into());
+    }
+    if data_len != MintRecordV1::LEN {
+        msg!("Unexpected mint record size: {}", data_len);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let old_record = MintRecordV1::try_from_slice(&mint_record_account.try_borrow_data()?)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    msg!("Mint address: {}", old_record.mint);
+
+    // Доплачиваем ренту за увеличение размера аккаунта
+    let rent = Rent::get()?;
+    let required_lamports = rent.minimum_balance(MintRecordV2::LEN);
+    let current_lamports = mint_record_account.lamports();
+    if current_lamports < required_lamports {
+        let top_up = required_lamports - current_lamports;
+        msg!("Topping up rent: {} lamports", top_up);
+        invoke(
+            &system_instruction::transfer(admin.key, mint_record_account.key, top_up),
+            &[
+                admin.clone(),
+                mint_record_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    mint_record_account.realloc(MintRecordV2::LEN, false)?;
+
+    // Слот и время минта старых записей неизвестны
+    MintRecordV2 {
+        key: Key::MintRecordV2,
+        version: MintRecordV2::VERSION,
+        round: args.round,
+        mint: old_record.mint,
+        nft_number: args.nft_number,
+        minter: *wallet.key,
+        slot: 0,
+        unix_timestamp: 0,
+        bump,
+    }
+    .save(mint_record_account)?;
+
+    msg!("Mint record migrated successfully!");
+    Ok(())
+}
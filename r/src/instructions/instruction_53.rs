@@ -0,0 +1,112 @@
+// Инструкция 53: Создание битовой карты минтов раунда
+//
+// Карту можно создать только до начала минта в раунде: после этого инструкция 45
+// отмечает NFTnumber в карте и не создает отдельную запись о минте для каждого
+// кошелька. Иначе в раунде смешались бы оба способа учета.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    rent::Rent,
+    sysvar::clock::Clock,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::CreateClaimBitmapArgsV1;
+use crate::state::{
+    ClaimBitmap, Key, ProgramConfig, RoundConfig, CLAIM_BITMAP_SEED, MAX_CLAIM_BITMAP_CAPACITY,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateClaimBitmapArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 53: Create claim bitmap");
+    msg!("Round: {}", args.round);
+    msg!("Capacity: {}", args.capacity);
+
+    let accounts_iter = &mut accounts.iter();
+
+    let claim_bitmap_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let round_config_account = next_account_info(accounts_iter)?;
+
+    // Создавать битовые карты может только администратор
+    ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    // До начала раунда минтов еще нет, все они будут учтены в карте
+    let round_config = RoundConfig::load(round_config_account, args.round, program_id)?;
+    let now = Clock::get()?.unix_timestamp;
+    if round_config.has_started(now) {
+        msg!("Claim bitmap must be created before round {} starts: now {}, start {}",
+             args.round, now, round_config.start_timestamp);
+        return Err(RError::RoundAlreadyStarted.into());
+    }
+
+    if args.capacity == 0 || args.capacity > MAX_CLAIM_BITMAP_CAPACITY {
+        msg!("Claim bitmap capacity must be between 1 and {}", MAX_CLAIM_BITMAP_CAPACITY);
+        return Err(RError::InvalidClaimBitmapCapacity.into());
+    }
+
+    // Проверяем адрес PDA битовой карты
+    let (expected_claim_bitmap_address, bump_seed) = ClaimBitmap::find_address(args.round, program_id);
+    if claim_bitmap_account.key != &expected_claim_bitmap_address {
+        msg!("Invalid claim bitmap account address");
+        msg!("Expected: {}", expected_claim_bitmap_address);
+        msg!("Received: {}", claim_bitmap_account.key);
+        return Err(RError::InvalidClaimBitmapAddress.into());
+    }
+
+    if !claim_bitmap_account.data_is_empty() {
+        msg!("Claim bitmap for round {} already exists", args.round);
+        return Err(RError::ClaimBitmapAlreadyExists.into());
+    }
+
+    // Создаем PDA битовой карты (биты изначально нулевые)
+    msg!("Creating claim bitmap PDA...");
+    let claim_bitmap_size = ClaimBitmap::len(args.capacity);
+    let rent = Rent::get()?;
+    let claim_bitmap_lamports = rent.minimum_balance(claim_bitmap_size);
+
+    let claim_bitmap_signature_seeds = &[
+        CLAIM_BITMAP_SEED,
+        &args.round.to_le_bytes(),
+        &[bump_seed],
+    ];
+    let claim_bitmap_signers = &[&claim_bitmap_signature_seeds[..]];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            admin.key,
+            claim_bitmap_account.key,
+            claim_bitmap_lamports,
+            claim_bitmap_size as u64,
+            program_id,
+        ),
+        &[
+            admin.clone(),
+            claim_bitmap_account.clone(),
+            system_program.clone(),
+        ],
+        claim_bitmap_signers,
+    )?;
+
+    ClaimBitmap {
+        key: Key::ClaimBitmap,
+        round: args.round,
+        capacity: args.capacity,
+        bump: bump_seed,
+    }
+    .save(claim_bitmap_account)?;
+
+    msg!("Claim bitmap for round {} created successfully!", args.round);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Инструкция 54: Постановка на паузу (глобально или для одного раунда)
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    msg,
+};
+
+use crate::error::RError;
+use crate::instruction::PauseArgsV1;
+use crate::state::{ProgramConfig, RoundConfig, PAUSE_ALL};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: PauseArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 54: Pause");
+    set_paused(program_id, accounts, args, true)
+}
+
+// Общая логика инструкций 54 и 55: pause = true ставит биты scope, false - снимает
+pub(crate) fn set_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: PauseArgsV1,
+    pause: bool,
+) -> ProgramResult {
+    msg!("Scope: {:#05b}", args.scope);
+
+    if args.scope == 0 || args.scope & !PAUSE_ALL != 0 {
+        msg!("Invalid pause scope: {}", args.scope);
+        return Err(RError::InvalidPauseScope.into());
+    }
+
+    let accounts_iter = &mut accounts.iter();
+
+    let program_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+
+    // Ставить и снимать паузу может только администратор
+    let mut program_config = ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    match args.round {
+        // Пауза одного раунда хранится в его конфигурации
+        Some(round) => {
+            msg!("Round: {}", round);
+            let round_config_account = next_account_info(accounts_iter)?;
+            let mut round_config = RoundConfig::load(round_config_account, round, program_id)?;
+            round_config.paused = apply_scope(round_config.paused, args.scope, pause);
+            round_config.save(round_config_account)?;
+            msg!("Round {} pause flags: {:#05b}", round, round_config.paused);
+        }
+        // Глобальная пауза хранится в конфигурации программы
+        None => {
+            program_config.paused = apply_scope(program_config.paused, args.scope, pause);
+            program_config.save(program_config_account)?;
+            msg!("Global pause flags: {:#05b}", program_config.paused);
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_scope(paused: u8, scope: u8, pause: bool) -> u8 {
+    if pause {
+        paused | scope
+    } else {
+        paused & !scope
+    }
+}
@@ -0,0 +1,20 @@
+// Инструкция 55: Снятие паузы (глобально или для одного раунда)
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    msg,
+};
+
+use crate::instruction::PauseArgsV1;
+use crate::instructions::instruction_54::set_paused;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: PauseArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 55: Unpause");
+    set_paused(program_id, accounts, args, false)
+}
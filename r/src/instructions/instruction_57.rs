@@ -0,0 +1,95 @@
+// Инструкция 57: Создание или замена распределения выручки между кошельками команды
+//
+// При замене списка получателей счетчики total_distributed и dust сохраняются.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::SetSplitConfigArgsV1;
+use crate::state::{Key, ProgramConfig, SplitConfig, SPLIT_CONFIG_SEED};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetSplitConfigArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 57: Set split config");
+    msg!("Recipients: {}", args.recipients.len());
+
+    let accounts_iter = &mut accounts.iter();
+
+    let split_config_account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+
+    // Менять распределение может только администратор
+    ProgramConfig::assert_admin(program_config_account, admin, program_id)?;
+
+    // Проверяем адрес PDA распределения
+    let (expected_split_config_address, bump_seed) = SplitConfig::find_address(program_id);
+    if split_config_account.key != &expected_split_config_address {
+        msg!("Invalid split config account address");
+        msg!("Expected: {}", expected_split_config_address);
+        msg!("Received: {}", split_config_account.key);
+        return Err(RError::InvalidSplitConfigAddress.into());
+    }
+
+    let config = if split_config_account.data_is_empty() {
+        // Создаем PDA распределения с местом под максимальное количество получателей
+        msg!("Creating split config PDA...");
+        let rent = Rent::get()?;
+        let split_config_lamports = rent.minimum_balance(SplitConfig::LEN);
+
+        let split_config_signature_seeds = &[SPLIT_CONFIG_SEED, &[bump_seed]];
+        let split_config_signers = &[&split_config_signature_seeds[..]];
+
+        invoke_signed(
+            &system_instruction::create_account(
+                admin.key,
+                split_config_account.key,
+                split_config_lamports,
+                SplitConfig::LEN as u64,
+                program_id,
+            ),
+            &[
+                admin.clone(),
+                split_config_account.clone(),
+                system_program.clone(),
+            ],
+            split_config_signers,
+        )?;
+
+        SplitConfig {
+            key: Key::SplitConfig,
+            bump: bump_seed,
+            total_distributed: 0,
+            dust: 0,
+            recipients: args.recipients,
+        }
+    } else {
+        msg!("Replacing split config recipients...");
+        SplitConfig {
+            recipients: args.recipients,
+            ..SplitConfig::load(split_config_account, program_id)?
+        }
+    };
+
+    config.validate()?;
+    for recipient in config.recipients.iter() {
+        msg!("Recipient {}: {} bps", recipient.address, recipient.basis_points);
+    }
+    config.save(split_config_account)?;
+
+    msg!("Split config saved successfully!");
+    Ok(())
+}
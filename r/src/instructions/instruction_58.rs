@@ -0,0 +1,123 @@
+// Инструкция 58: Распределение выручки между получателями из SplitConfig
+//
+// Вызвать может кто угодно. Распределяется баланс источника (казна или PDA
+// ["mint_authority"]) сверх минимума для ренты: каждый получатель получает
+// balance * basis_points / 10000 с округлением вниз. Долю, после которой у
+// получателя не набирается минимум для ренты, не переводим: иначе перевод на
+// пустой аккаунт отклоняется и распределение блокируется. Остаток от округления
+// и пропущенные доли (dust) остаются на источнике и входят в следующее
+// распределение. Поэтому SplitConfig.dust не накапливается, а хранит остаток
+// последнего распределения: иначе одни и те же лампорты учитывались бы при каждом
+// вызове.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    sysvar::clock::Clock,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::events::{DistributeEvent, REvent, SplitPayout};
+use crate::state::{
+    assert_not_paused, assert_withdraw_source, ProgramConfig, SplitConfig, PAUSE_WITHDRAW,
+    SPLIT_TOTAL_BASIS_POINTS,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Instruction 58: Distribute revenue");
+
+    let accounts_iter = &mut accounts.iter();
+
+    let split_config_account = next_account_info(accounts_iter)?;
+    let source = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Распределение - тоже вывод средств и подчиняется паузе вывода
+    let program_config = ProgramConfig::load(program_config_account, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_WITHDRAW)?;
+
+    let mut config = SplitConfig::load(split_config_account, program_id)?;
+    let (source_seed, bump_seed) = assert_withdraw_source(source, program_id)?;
+
+    // На источнике остается минимум для ренты
+    let rent = Rent::get()?;
+    let rent_exempt_minimum = rent.minimum_balance(source.data_len());
+    let distributable = source.lamports().saturating_sub(rent_exempt_minimum);
+    if distributable == 0 {
+        msg!("Nothing to distribute");
+        return Err(RError::InvalidWithdrawAmount.into());
+    }
+    msg!("Distributing {} lamports from {}", distributable, source.key);
+
+    let source_signature_seeds = &[source_seed, &[bump_seed]];
+    let signers = &[&source_signature_seeds[..]];
+
+    // Аккаунты получателей передаются в порядке SplitConfig
+    let mut payouts = Vec::with_capacity(config.recipients.len());
+    for split_recipient in config.recipients.iter() {
+        let recipient = next_account_info(accounts_iter)?;
+        if recipient.key != &split_recipient.address {
+            msg!("Invalid split recipient provided");
+            msg!("Expected: {}", split_recipient.address);
+            msg!("Received: {}", recipient.key);
+            return Err(RError::SplitRecipientMismatch.into());
+        }
+
+        let share = (distributable as u128 * split_recipient.basis_points as u128
+            / SPLIT_TOTAL_BASIS_POINTS as u128) as u64;
+        let balance = recipient.lamports().saturating_add(share);
+        let amount = if balance < rent.minimum_balance(recipient.data_len()) {
+            msg!("Share {} for {} is below the rent-exempt minimum, carried as dust", share, recipient.key);
+            0
+        } else {
+            share
+        };
+        if amount > 0 {
+            invoke_signed(
+                &system_instruction::transfer(source.key, recipient.key, amount),
+                &[
+                    source.clone(),
+                    recipient.clone(),
+                    system_program.clone(),
+                ],
+                signers,
+            )?;
+        }
+        msg!("Sent {} lamports to {}", amount, recipient.key);
+
+        payouts.push(SplitPayout {
+            recipient: *recipient.key,
+            amount,
+        });
+    }
+
+    let amount: u64 = payouts.iter().map(|payout| payout.amount).sum();
+    let dust = distributable - amount;
+    config.dust = dust;
+    config.total_distributed = config.total_distributed.saturating_add(amount);
+    config.save(split_config_account)?;
+
+    let clock = Clock::get()?;
+    REvent::Distribute(DistributeEvent {
+        source: *source.key,
+        amount,
+        dust,
+        payouts,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    })
+    .emit()?;
+
+    msg!("Distributed {} lamports, dust {}", amount, dust);
+    Ok(())
+}
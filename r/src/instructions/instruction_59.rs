@@ -0,0 +1,110 @@
+// Инструкция 59: Пакетное обновление метаданных NFT
+//
+// Каждое обновление работает как инструкция 43 и берет пару metadata/mint из
+// оставшихся аккаунтов. Обновления выполняются, пока хватает вычислительного
+// бюджета; результат по каждому NFT возвращается через return data.
+//
+// Failed означает только ошибку проверок до CPI (адрес метаданных, минт,
+// неизменяемые метаданные и т.п.). Ошибка самого CPI UpdateV1 в Token Metadata
+// перехватить нельзя: она отменяет всю транзакцию вместе с уже выполненными
+// обновлениями.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    compute_units::sol_remaining_compute_units,
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+};
+
+use crate::error::RError;
+use crate::instruction::{BatchUpdateMetadataArgsV1, BatchUpdateStatus};
+use crate::instructions::instruction_43::update_metadata;
+use crate::state::{
+    assert_not_paused, assert_token_metadata_program, ProgramConfig, BATCH_UPDATE_COMPUTE_UNITS,
+    PAUSE_UPDATE,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: BatchUpdateMetadataArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 59: Batch update metadata");
+    msg!("Updates: {}", args.updates.len());
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Получаем все необходимые аккаунты
+    let program_authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let sysvar_instructions = next_account_info(accounts_iter)?;
+    let token_metadata_program = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+
+    // Обновлять метаданные может только администратор (он же payer)
+    let program_config = ProgramConfig::assert_admin(program_config_account, payer, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_UPDATE)?;
+    assert_token_metadata_program(token_metadata_program)?;
+
+    // На каждое обновление - пара metadata/mint
+    let pairs = accounts_iter.as_slice();
+    if pairs.len() != args.updates.len() * 2 {
+        msg!("Expected {} metadata/mint accounts", args.updates.len() * 2);
+        msg!("Received: {}", pairs.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Проверяем, что program_authority это правильный PDA
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(
+        &[b"mint_authority"],
+        program_id
+    );
+    if program_authority.key != &expected_authority {
+        msg!("Invalid program authority provided");
+        return Err(RError::InvalidProgramAuthority.into());
+    }
+
+    // Создаем authority seeds для подписи
+    let authority_signature_seeds = &[
+        b"mint_authority".as_ref(),
+        &[bump_seed],
+    ];
+    let signers = &[&authority_signature_seeds[..]];
+
+    let mut statuses = vec![BatchUpdateStatus::Skipped; args.updates.len()];
+    for (i, (update, accounts)) in args.updates.into_iter().zip(pairs.chunks(2)).enumerate() {
+        // Оставшиеся обновления не выполняем, если бюджета может не хватить
+        let remaining_compute_units = sol_remaining_compute_units();
+        if remaining_compute_units < BATCH_UPDATE_COMPUTE_UNITS {
+            msg!("Compute budget exhausted: {} units left, skipping updates from {}", remaining_compute_units, i);
+            break;
+        }
+
+        msg!("Updating metadata {} of mint {}", i, update.mint);
+        statuses[i] = match update_metadata(
+            &accounts[0],
+            &accounts[1],
+            program_authority,
+            payer,
+            system_program,
+            sysvar_instructions,
+            signers,
+            update,
+        ) {
+            Ok(()) => BatchUpdateStatus::Updated,
+            Err(err) => {
+                msg!("Update {} failed: {}", i, err);
+                BatchUpdateStatus::Failed
+            }
+        };
+    }
+
+    let updated = statuses.iter().filter(|status| **status == BatchUpdateStatus::Updated).count();
+    msg!("Updated {} of {} metadata accounts", updated, statuses.len());
+    set_return_data(&borsh::to_vec(&statuses)?);
+    Ok(())
+}
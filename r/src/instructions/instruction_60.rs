@@ -0,0 +1,141 @@
+// Инструкция 60: Отложенное раскрытие метаданных NFT (без подписи администратора)
+//
+// NFT минтятся с временным URI раунда. Администратор заранее фиксирует в
+// конфигурации раунда корень Merkle манифеста (NFTnumber -> URI). Любой может
+// раскрыть NFT, предъявив его настоящий URI с доказательством против этого корня.
+// NFT определяется по названию из шаблона раунда и проверенной коллекции; в шаблон
+// раунда с раскрытием входят NFTnumber и номер раунда, поэтому NFT другого раунда
+// под этот манифест не подходит.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    msg,
+};
+use mpl_token_metadata::accounts::Metadata;
+
+use crate::error::RError;
+use crate::instruction::{RevealArgsV1, UpdateMetadataArgsV2};
+use crate::instructions::instruction_43::update_metadata;
+use crate::merkle::{reveal_leaf, verify_merkle_proof};
+use crate::state::{
+    assert_not_paused, assert_token_metadata_program, ProgramConfig, RoundConfig, MAX_URI_BASE_LENGTH,
+    PAUSE_UPDATE,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RevealArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 60: Reveal");
+    msg!("Round: {}, NFTnumber: {}", args.round, args.nft_number);
+    msg!("URI: {}", args.uri);
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Получаем все необходимые аккаунты
+    let metadata_account = next_account_info(accounts_iter)?;
+    let mint_account = next_account_info(accounts_iter)?;
+    let program_authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let sysvar_instructions = next_account_info(accounts_iter)?;
+    let token_metadata_program = next_account_info(accounts_iter)?;
+    let round_config_account = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+
+    let program_config = ProgramConfig::load(program_config_account, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_UPDATE)?;
+    assert_token_metadata_program(token_metadata_program)?;
+    let round_config = RoundConfig::load(round_config_account, args.round, program_id)?;
+    assert_not_paused(round_config.paused, PAUSE_UPDATE)?;
+
+    if round_config.reveal_root == [0u8; 32] {
+        msg!("Reveal is not configured for round {}", args.round);
+        return Err(RError::RevealNotConfigured.into());
+    }
+    if args.uri.len() > MAX_URI_BASE_LENGTH {
+        msg!("URI is too long: {} > {}", args.uri.len(), MAX_URI_BASE_LENGTH);
+        return Err(RError::UriTooLong.into());
+    }
+
+    // URI должен входить в зафиксированный манифест под этим NFTnumber
+    let leaf = reveal_leaf(args.nft_number, &args.uri);
+    if !verify_merkle_proof(leaf, &args.proof, round_config.reveal_root) {
+        msg!("Invalid reveal proof for NFTnumber {} in round {}", args.nft_number, args.round);
+        return Err(RError::InvalidRevealProof.into());
+    }
+
+    // NFT должен быть из коллекции раунда и носить название с этим NFTnumber и раундом
+    let (expected_metadata, _) = Metadata::find_pda(mint_account.key);
+    if metadata_account.key != &expected_metadata || metadata_account.owner != &mpl_token_metadata::ID {
+        msg!("Invalid metadata account provided");
+        msg!("Expected: {}", expected_metadata);
+        msg!("Received: {}", metadata_account.key);
+        return Err(RError::InvalidMetadataAccount.into());
+    }
+    let metadata = Metadata::try_from(metadata_account).map_err(|err| {
+        msg!("Failed to deserialize metadata: {}", err);
+        RError::InvalidMetadataAccount
+    })?;
+    let expected_collection = round_config.collection(&program_config);
+    let in_collection = metadata
+        .collection
+        .as_ref()
+        .is_some_and(|collection| collection.verified && collection.key == expected_collection);
+    if !in_collection {
+        msg!("NFT is not a verified member of collection {}", expected_collection);
+        return Err(RError::RevealMintMismatch.into());
+    }
+    let expected_name = round_config.nft_name(args.nft_number);
+    let name = metadata.name.trim_end_matches('\0');
+    if name != expected_name {
+        msg!("NFT name does not match NFTnumber {} in round {}", args.nft_number, args.round);
+        msg!("Expected: {}", expected_name);
+        msg!("Received: {}", name);
+        return Err(RError::RevealMintMismatch.into());
+    }
+
+    // Проверяем, что program_authority это правильный PDA
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(
+        &[b"mint_authority"],
+        program_id
+    );
+    if program_authority.key != &expected_authority {
+        msg!("Invalid program authority provided");
+        return Err(RError::InvalidProgramAuthority.into());
+    }
+
+    // Создаем authority seeds для подписи
+    let authority_signature_seeds = &[
+        b"mint_authority".as_ref(),
+        &[bump_seed],
+    ];
+    let signers = &[&authority_signature_seeds[..]];
+
+    // Меняется только URI, остальные поля остаются как есть
+    update_metadata(
+        metadata_account,
+        mint_account,
+        program_authority,
+        payer,
+        system_program,
+        sysvar_instructions,
+        signers,
+        UpdateMetadataArgsV2 {
+            mint: *mint_account.key,
+            creators: None,
+            name: None,
+            symbol: None,
+            uri: Some(args.uri),
+            seller_fee_basis_points: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        },
+    )?;
+
+    msg!("NFT {} revealed successfully!", mint_account.key);
+    Ok(())
+}
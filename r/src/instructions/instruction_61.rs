@@ -0,0 +1,207 @@
+// Инструкция 61: Минт нескольких pNFT одного кошелька по мультидоказательству Merkle
+//
+// Победитель с несколькими NFTnumber в раунде (повторяющийся player в d3.json)
+// доказывает все свои листья одним мультидоказательством и минтит их в одной
+// транзакции. Учет ведется только в битовой карте раунда: запись о минте
+// ["minted", round, wallet] допускает лишь один NFT на кошелек.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    compute_units::sol_remaining_compute_units,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+    sysvar::clock::Clock,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::MintWithMultiproofArgsV1;
+use crate::instructions::instruction_45::{
+    assert_collection_accounts, mint_round_nft, pay_mint_price, NftAccounts, RoundMintAccounts,
+};
+use crate::merkle::{round_leaf, verify_merkle_multiproof};
+use crate::state::{
+    assert_not_paused, assert_treasury, ClaimBitmap, MerkleLeafVersion, ProgramConfig, RoundConfig,
+    MULTI_MINT_COMPUTE_UNITS, PAUSE_MINT,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MintWithMultiproofArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 61: Mint with multiproof");
+
+    let MintWithMultiproofArgsV1 { round: round_number, nft_numbers, leaf_indices, leaf_count, proof } = args;
+    msg!("Using round number: {}", round_number);
+    msg!("Using NFTnumbers: {:?}", nft_numbers);
+
+    if nft_numbers.is_empty() || nft_numbers.len() != leaf_indices.len() {
+        msg!("Got {} NFT numbers and {} leaf indices", nft_numbers.len(), leaf_indices.len());
+        return Err(RError::InvalidMultiMintArgs.into());
+    }
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Получаем все необходимые аккаунты
+    let program_authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let sysvar_instructions = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let spl_ata_program = next_account_info(accounts_iter)?;
+    let token_metadata_program = next_account_info(accounts_iter)?;
+    let rent_sysvar = next_account_info(accounts_iter)?;
+    let collection_mint = next_account_info(accounts_iter)?;
+    let collection_metadata = next_account_info(accounts_iter)?;
+    let collection_master_edition = next_account_info(accounts_iter)?;
+    let round_config_account = next_account_info(accounts_iter)?;
+    let claim_bitmap_account = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let treasury = next_account_info(accounts_iter)?;
+
+    // Аккаунты каждого NFT: metadata, master edition, mint, token account, token record
+    let mut nfts = Vec::with_capacity(nft_numbers.len());
+    for _ in 0..nft_numbers.len() {
+        let nft = NftAccounts {
+            metadata: next_account_info(accounts_iter)?,
+            master_edition: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            token_account: next_account_info(accounts_iter)?,
+            token_record: next_account_info(accounts_iter)?,
+        };
+        if !nft.mint.is_signer {
+            msg!("Mint account {} must be a signer", nft.mint.key);
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        nfts.push(nft);
+    }
+
+    // Минт можно поставить на паузу глобально (инструкция 54)
+    let program_config = ProgramConfig::load(program_config_account, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_MINT)?;
+
+    if !payer.is_signer {
+        msg!("Payer must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Все минты должны уложиться в вычислительный бюджет транзакции
+    let required_units = MULTI_MINT_COMPUTE_UNITS * nfts.len() as u64;
+    let remaining_units = sol_remaining_compute_units();
+    if remaining_units < required_units {
+        msg!("Minting {} NFTs needs {} compute units, {} left", nfts.len(), required_units, remaining_units);
+        return Err(RError::InsufficientComputeBudget.into());
+    }
+
+    // Проверяем, что program_authority это правильный PDA
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(
+        &[b"mint_authority"],
+        program_id
+    );
+    if program_authority.key != &expected_authority {
+        msg!("Invalid program authority provided");
+        return Err(RError::InvalidProgramAuthority.into());
+    }
+
+    let round_config = RoundConfig::load(round_config_account, round_number, program_id)?;
+
+    // Минт разрешен только в окне раунда и если раунд не на паузе
+    let clock = Clock::get()?;
+    round_config.assert_mint_window(clock.unix_timestamp)?;
+    assert_not_paused(round_config.paused, PAUSE_MINT)?;
+
+    // Коллекция раунда (или коллекция программы, если у раунда она не задана)
+    round_config.assert_collection_mint(&program_config, collection_mint)?;
+
+    // Проверяем, что token_program совпадает с программой токенов раунда
+    let expected_token_program = round_config.token_program_id();
+    if token_program.key != &expected_token_program {
+        msg!("Invalid Token program ID provided");
+        msg!("Expected: {}", expected_token_program);
+        msg!("Received: {}", token_program.key);
+        return Err(RError::InvalidTokenProgram.into());
+    }
+
+    // Раунды с лимитом минта на кошелек учитываются записью лимита (инструкция 62)
+    if round_config.leaf_version == MerkleLeafVersion::Allowance {
+        msg!("Round {} uses allowance leaves, use instruction 62", round_number);
+        return Err(RError::UnsupportedLeafVersion.into());
+    }
+
+    // Все листья кошелька проверяются против корня раунда одним доказательством
+    msg!("Verifying Merkle multiproof ({:?} leaves)...", round_config.leaf_version);
+    let leaves: Vec<(u32, [u8; 32])> = leaf_indices
+        .iter()
+        .zip(nft_numbers.iter())
+        .map(|(index, nft_number)| {
+            let leaf = round_leaf(round_config.leaf_version, program_id, round_number, payer.key, *nft_number);
+            (*index, leaf)
+        })
+        .collect();
+    if !verify_merkle_multiproof(round_config.leaf_version, leaf_count, &leaves, &proof, round_config.merkle_root) {
+        msg!("Invalid Merkle multiproof for address: {} in round {}", payer.key, round_number);
+        return Err(RError::NotOnAllowlist.into());
+    }
+    msg!("Merkle multiproof verified successfully for round {}!", round_number);
+
+    // Несколько NFT одного кошелька учитываются только битовой картой
+    let bitmap = ClaimBitmap::load(claim_bitmap_account, round_number, program_id)?.ok_or_else(|| {
+        msg!("Round {} has no claim bitmap", round_number);
+        RError::ClaimBitmapRequired
+    })?;
+
+    assert_collection_accounts(
+        token_metadata_program,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    )?;
+
+    // Оплата минта всех NFT поступает в казну; аккаунты оплаты токеном идут после аккаунтов NFT
+    assert_treasury(treasury, program_id)?;
+    let quantity = nfts.len() as u64;
+    pay_mint_price(&round_config, quantity, payer, treasury, system_program, spl_ata_program, accounts_iter)?;
+
+    // Создаем authority seeds для подписи
+    let authority_signature_seeds = &[
+        b"mint_authority".as_ref(),
+        &[bump_seed],
+    ];
+    let signers = &[&authority_signature_seeds[..]];
+
+    let rent = Rent::get()?;
+    let shared_accounts = RoundMintAccounts {
+        program_authority,
+        payer,
+        system_program,
+        sysvar_instructions,
+        token_program,
+        token_owner: payer,
+        spl_ata_program,
+        token_metadata_program,
+        rent_sysvar,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    };
+
+    for (nft, nft_number) in nfts.iter().zip(nft_numbers) {
+        // Повтор NFTnumber в аргументах тоже отсекается битовой картой
+        if bitmap.is_claimed(claim_bitmap_account, nft_number)? {
+            msg!("NFTnumber {} has already been minted in round {}", nft_number, round_number);
+            return Err(RError::NftNumberAlreadyClaimed.into());
+        }
+        msg!("Marking NFTnumber {} in claim bitmap...", nft_number);
+        bitmap.set_claimed(claim_bitmap_account, nft_number)?;
+
+        mint_round_nft(&shared_accounts, nft, &round_config, nft_number, signers, &rent)?;
+        msg!("pNFT {} minted for NFTnumber {}", nft.mint.key, nft_number);
+    }
+
+    msg!("{} pNFTs created, minted and added to collection with Merkle multiproof verification!", quantity);
+    Ok(())
+}
@@ -0,0 +1,244 @@
+// Инструкция 62: Минт pNFT в пределах лимита кошелька
+//
+// В раундах формата Allowance лист один на кошелек и содержит все его NFTnumber
+// (лотерея может дать кошельку несколько билетов). Запись лимита
+// ["allowance", round, wallet] хранит, сколько уже заминчено: каждый вызов минтит
+// следующие quantity номеров из листа, пока лимит не исчерпан.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    compute_units::sol_remaining_compute_units,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    msg,
+    system_instruction,
+    sysvar::clock::Clock,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::RError;
+use crate::instruction::MintWithAllowanceArgsV1;
+use crate::instructions::instruction_45::{
+    assert_collection_accounts, mint_round_nft, pay_mint_price, NftAccounts, RoundMintAccounts,
+};
+use crate::merkle::{allowance_leaf, verify_merkle_proof_v2};
+use crate::state::{
+    assert_not_paused, assert_treasury, AllowanceRecord, Key, MerkleLeafVersion, ProgramConfig,
+    RoundConfig, ALLOWANCE_RECORD_SEED, MULTI_MINT_COMPUTE_UNITS, PAUSE_MINT,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MintWithAllowanceArgsV1,
+) -> ProgramResult {
+    msg!("Instruction 62: Mint with allowance");
+
+    let MintWithAllowanceArgsV1 { round: round_number, nft_numbers, quantity, proof } = args;
+    msg!("Using round number: {}", round_number);
+    msg!("Allowance: {}, quantity: {}", nft_numbers.len(), quantity);
+
+    if quantity == 0 {
+        return Err(RError::InvalidMintQuantity.into());
+    }
+
+    // Количество NFTnumber входит в лист как u16 - больший лимит не хешируем
+    if nft_numbers.len() > u16::MAX as usize {
+        msg!("Allowance of {} NFT numbers exceeds {}", nft_numbers.len(), u16::MAX);
+        return Err(RError::AllowanceTooLarge.into());
+    }
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Получаем все необходимые аккаунты
+    let program_authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let sysvar_instructions = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let spl_ata_program = next_account_info(accounts_iter)?;
+    let token_metadata_program = next_account_info(accounts_iter)?;
+    let rent_sysvar = next_account_info(accounts_iter)?;
+    let collection_mint = next_account_info(accounts_iter)?;
+    let collection_metadata = next_account_info(accounts_iter)?;
+    let collection_master_edition = next_account_info(accounts_iter)?;
+    let round_config_account = next_account_info(accounts_iter)?;
+    let allowance_record_account = next_account_info(accounts_iter)?;
+    let program_config_account = next_account_info(accounts_iter)?;
+    let treasury = next_account_info(accounts_iter)?;
+
+    // Аккаунты каждого NFT: metadata, master edition, mint, token account, token record
+    let mut nfts = Vec::with_capacity(quantity as usize);
+    for _ in 0..quantity {
+        let nft = NftAccounts {
+            metadata: next_account_info(accounts_iter)?,
+            master_edition: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            token_account: next_account_info(accounts_iter)?,
+            token_record: next_account_info(accounts_iter)?,
+        };
+        if !nft.mint.is_signer {
+            msg!("Mint account {} must be a signer", nft.mint.key);
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        nfts.push(nft);
+    }
+
+    // Минт можно поставить на паузу глобально (инструкция 54)
+    let program_config = ProgramConfig::load(program_config_account, program_id)?;
+    assert_not_paused(program_config.paused, PAUSE_MINT)?;
+
+    if !payer.is_signer {
+        msg!("Payer must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Все минты должны уложиться в вычислительный бюджет транзакции
+    let required_units = MULTI_MINT_COMPUTE_UNITS * quantity as u64;
+    let remaining_units = sol_remaining_compute_units();
+    if remaining_units < required_units {
+        msg!("Minting {} NFTs needs {} compute units, {} left", quantity, required_units, remaining_units);
+        return Err(RError::InsufficientComputeBudget.into());
+    }
+
+    // Проверяем, что program_authority это правильный PDA
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(
+        &[b"mint_authority"],
+        program_id
+    );
+    if program_authority.key != &expected_authority {
+        msg!("Invalid program authority provided");
+        return Err(RError::InvalidProgramAuthority.into());
+    }
+
+    let round_config = RoundConfig::load(round_config_account, round_number, program_id)?;
+
+    // Минт разрешен только в окне раунда и если раунд не на паузе
+    let clock = Clock::get()?;
+    round_config.assert_mint_window(clock.unix_timestamp)?;
+    assert_not_paused(round_config.paused, PAUSE_MINT)?;
+
+    // Коллекция раунда (или коллекция программы, если у раунда она не задана)
+    round_config.assert_collection_mint(&program_config, collection_mint)?;
+
+    // Проверяем, что token_program совпадает с программой токенов раунда
+    let expected_token_program = round_config.token_program_id();
+    if token_program.key != &expected_token_program {
+        msg!("Invalid Token program ID provided");
+        msg!("Expected: {}", expected_token_program);
+        msg!("Received: {}", token_program.key);
+        return Err(RError::InvalidTokenProgram.into());
+    }
+
+    if round_config.leaf_version != MerkleLeafVersion::Allowance {
+        msg!("Round {} uses {:?} leaves, allowance leaves required", round_number, round_config.leaf_version);
+        return Err(RError::UnsupportedLeafVersion.into());
+    }
+
+    // Лист кошелька фиксирует его лимит и номера NFT
+    msg!("Verifying Merkle proof...");
+    let leaf = allowance_leaf(program_id, round_number, payer.key, &nft_numbers)
+        .ok_or(RError::AllowanceTooLarge)?;
+    if !verify_merkle_proof_v2(leaf, &proof, round_config.merkle_root) {
+        msg!("Invalid Merkle proof for address: {} with allowance {} in round {}",
+             payer.key, nft_numbers.len(), round_number);
+        return Err(RError::NotOnAllowlist.into());
+    }
+    msg!("Merkle proof verified successfully for round {}!", round_number);
+
+    let rent = Rent::get()?;
+
+    // Запись лимита создается при первом минте кошелька в раунде
+    let mut record = match AllowanceRecord::load(allowance_record_account, round_number, payer.key, program_id)? {
+        Some(record) => record,
+        None => {
+            msg!("Creating allowance record PDA...");
+            let (_, record_bump) = AllowanceRecord::find_address(round_number, payer.key, program_id);
+            let record_signature_seeds = &[
+                ALLOWANCE_RECORD_SEED,
+                &round_number.to_le_bytes(),
+                payer.key.as_ref(),
+                &[record_bump],
+            ];
+            let record_signers = &[&record_signature_seeds[..]];
+
+            invoke_signed(
+                &system_instruction::create_account(
+                    payer.key,
+                    allowance_record_account.key,
+                    rent.minimum_balance(AllowanceRecord::LEN),
+                    AllowanceRecord::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer.clone(),
+                    allowance_record_account.clone(),
+                    system_program.clone(),
+                ],
+                record_signers,
+            )?;
+
+            AllowanceRecord {
+                key: Key::AllowanceRecord,
+                round: round_number,
+                wallet: *payer.key,
+                minted: 0,
+                bump: record_bump,
+            }
+        }
+    };
+
+    let start = record.minted as usize;
+    let end = start + quantity as usize;
+    if end > nft_numbers.len() {
+        msg!("Allowance exceeded: minted {}, requested {}, allowance {}", start, quantity, nft_numbers.len());
+        return Err(RError::AllowanceExceeded.into());
+    }
+
+    assert_collection_accounts(
+        token_metadata_program,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    )?;
+
+    // Оплата минта всех NFT поступает в казну; аккаунты оплаты токеном идут после аккаунтов NFT
+    assert_treasury(treasury, program_id)?;
+    pay_mint_price(&round_config, quantity as u64, payer, treasury, system_program, spl_ata_program, accounts_iter)?;
+
+    // Создаем authority seeds для подписи
+    let authority_signature_seeds = &[
+        b"mint_authority".as_ref(),
+        &[bump_seed],
+    ];
+    let signers = &[&authority_signature_seeds[..]];
+
+    let shared_accounts = RoundMintAccounts {
+        program_authority,
+        payer,
+        system_program,
+        sysvar_instructions,
+        token_program,
+        token_owner: payer,
+        spl_ata_program,
+        token_metadata_program,
+        rent_sysvar,
+        collection_mint,
+        collection_metadata,
+        collection_master_edition,
+    };
+
+    for (nft, nft_number) in nfts.iter().zip(&nft_numbers[start..end]) {
+        mint_round_nft(&shared_accounts, nft, &round_config, *nft_number, signers, &rent)?;
+        msg!("pNFT {} minted for NFTnumber {}", nft.mint.key, nft_number);
+    }
+
+    record.minted = u16::try_from(end).map_err(|_| RError::AllowanceTooLarge)?;
+    record.save(allowance_record_account)?;
+
+    msg!("Minted {} of {} NFTs allowed for {} in round {}", end, nft_numbers.len(), payer.key, round_number);
+    Ok(())
+}
@@ -22,17 +22,30 @@ pub mod instruction_37;
 */
 /*
 pub mod instruction_38;
+*/
 
 pub mod instruction_39;
-*/
 
 /*
 pub mod instruction_40;
-pub mod instruction_41;
 */
 
 
 pub mod instruction_42;
 pub mod instruction_43;
-pub mod instruction_44;
-pub mod instruction_45;
\ No newline at end of file
+pub mod instruction_45;
+pub mod instruction_47;
+pub mod instruction_48;
+pub mod instruction_49;
+pub mod instruction_50;
+pub mod instruction_51;
+pub mod instruction_52;
+pub mod instruction_53;
+pub mod instruction_54;
+pub mod instruction_55;
+pub mod instruction_57;
+pub mod instruction_58;
+pub mod instruction_59;
+pub mod instruction_60;
+pub mod instruction_61;
+pub mod instruction_62;
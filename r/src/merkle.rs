@@ -0,0 +1,348 @@
+// Merkle-доказательства раундов и манифестов раскрытия
+//
+// Листья - sha256 от данных записи. Пара узлов хешируется в порядке возрастания:
+// sha256(min || max), поэтому доказательству не нужны флаги сторон. Так же дерево
+// строит merkletreejs с sortPairs и крейт r-merkle (корни и доказательства для раундов).
+//
+// Формат V2 (MerkleLeafVersion::V2) разделяет домены: лист начинается с
+// LEAF_PREFIX и включает программу и раунд, узел - с NODE_PREFIX. Доказательство
+// не переносится между раундами, а внутренний узел нельзя выдать за лист.
+// Формат Allowance использует те же префиксы, но лист один на кошелек и содержит
+// все его NFTnumber (allowance_leaf).
+
+use solana_program::{
+    hash::{hash, hashv},
+    pubkey::Pubkey,
+};
+
+use crate::state::MerkleLeafVersion;
+
+// Мультидоказательство проверяет несколько листьев одним набором узлов. Листья
+// задаются индексами в отсортированном нижнем уровне дерева из leaf_count листьев.
+// По индексам известна форма дерева: на каждом уровне узел хешируется с соседом,
+// если сосед тоже известен, иначе со следующим узлом proof; непарный последний узел
+// поднимается без изменений. proof - недостающие соседи снизу вверх и слева направо
+// в пределах уровня, все узлы должны быть использованы.
+
+// Префиксы доменов формата V2
+pub const LEAF_PREFIX: u8 = 0x00;
+pub const NODE_PREFIX: u8 = 0x01;
+
+// Лист ранних раундов без NFTnumber: sha256(wallet). Такие листья проверяла
+// только инструкция 44, она удалена: функция нужна лишь для пересчета старых корней
+pub fn wallet_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hash(wallet.as_ref()).to_bytes()
+}
+
+// Лист раунда с NFTnumber (инструкция 45): sha256(wallet || NFTnumber LE)
+pub fn wallet_nft_leaf(wallet: &Pubkey, nft_number: u16) -> [u8; 32] {
+    let mut combined_data = Vec::with_capacity(34); // 32 байта для адреса + 2 байта для NFTnumber
+    combined_data.extend_from_slice(wallet.as_ref());
+    combined_data.extend_from_slice(&nft_number.to_le_bytes());
+    hash(&combined_data).to_bytes()
+}
+
+// Лист раунда V2: sha256(0x00 || program_id || round LE || wallet || NFTnumber LE)
+pub fn wallet_nft_leaf_v2(program_id: &Pubkey, round: u64, wallet: &Pubkey, nft_number: u16) -> [u8; 32] {
+    hashv(&[
+        &[LEAF_PREFIX],
+        program_id.as_ref(),
+        &round.to_le_bytes(),
+        wallet.as_ref(),
+        &nft_number.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Лист с лимитом минта кошелька: sha256(0x00 || program_id || round LE || wallet ||
+// count u16 LE || NFTnumber LE * count). Лимит - количество NFTnumber; None, если
+// оно не помещается в u16
+pub fn allowance_leaf(program_id: &Pubkey, round: u64, wallet: &Pubkey, nft_numbers: &[u16]) -> Option<[u8; 32]> {
+    let count = u16::try_from(nft_numbers.len()).ok()?.to_le_bytes();
+    let numbers: Vec<u8> = nft_numbers.iter().flat_map(|number| number.to_le_bytes()).collect();
+    hashv(&[
+        &[LEAF_PREFIX],
+        program_id.as_ref(),
+        &round.to_le_bytes(),
+        wallet.as_ref(),
+        &count,
+        &numbers,
+    ])
+    .to_bytes()
+    .into()
+}
+
+// Лист манифеста раскрытия (инструкция 60): sha256(NFTnumber LE || URI)
+pub fn reveal_leaf(nft_number: u16, uri: &str) -> [u8; 32] {
+    let mut combined_data = Vec::with_capacity(2 + uri.len());
+    combined_data.extend_from_slice(&nft_number.to_le_bytes());
+    combined_data.extend_from_slice(uri.as_bytes());
+    hash(&combined_data).to_bytes()
+}
+
+// Родительский узел: sha256(min || max)
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (min, max) = if a <= b { (a, b) } else { (b, a) };
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(min);
+    bytes[32..].copy_from_slice(max);
+    hash(&bytes).to_bytes()
+}
+
+// Родительский узел V2: sha256(0x01 || min || max)
+pub fn hash_pair_v2(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (min, max) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[NODE_PREFIX], min, max]).to_bytes()
+}
+
+// Проверяет доказательство: последовательно хеширует лист с узлами proof
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = hash_pair(&computed, node);
+    }
+    computed == root
+}
+
+// То же для формата V2
+pub fn verify_merkle_proof_v2(leaf: [u8; 32], proof: &[[u8; 32]], root: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = hash_pair_v2(&computed, node);
+    }
+    computed == root
+}
+
+// Лист кошелька с NFTnumber в формате листьев раунда
+pub fn round_leaf(
+    version: MerkleLeafVersion,
+    program_id: &Pubkey,
+    round: u64,
+    wallet: &Pubkey,
+    nft_number: u16,
+) -> [u8; 32] {
+    match version {
+        MerkleLeafVersion::V1 => wallet_nft_leaf(wallet, nft_number),
+        MerkleLeafVersion::V2 => wallet_nft_leaf_v2(program_id, round, wallet, nft_number),
+        MerkleLeafVersion::Allowance => {
+            allowance_leaf(program_id, round, wallet, &[nft_number]).expect("one NFTnumber fits in u16 count")
+        }
+    }
+}
+
+// Проверяет, что кошелек с NFTnumber входит в дерево раунда в его формате листьев
+pub fn verify_wallet_nft(
+    version: MerkleLeafVersion,
+    program_id: &Pubkey,
+    round: u64,
+    wallet: &Pubkey,
+    nft_number: u16,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    let leaf = round_leaf(version, program_id, round, wallet, nft_number);
+    match version {
+        MerkleLeafVersion::V1 => verify_merkle_proof(leaf, proof, root),
+        MerkleLeafVersion::V2 | MerkleLeafVersion::Allowance => {
+            verify_merkle_proof_v2(leaf, proof, root)
+        }
+    }
+}
+
+// Корень по мультидоказательству; None, если индексы или длина proof не согласованы
+fn multiproof_root(
+    leaf_count: u32,
+    leaves: &[(u32, [u8; 32])],
+    proof: &[[u8; 32]],
+    hash_node: fn(&[u8; 32], &[u8; 32]) -> [u8; 32],
+) -> Option<[u8; 32]> {
+    let mut nodes = leaves.to_vec();
+    nodes.sort_by_key(|(index, _)| *index);
+    // Индексы уникальны и лежат внутри дерева
+    if nodes.is_empty() || nodes.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return None;
+    }
+    if nodes.last()?.0 >= leaf_count {
+        return None;
+    }
+
+    let mut proof = proof.iter();
+    let mut layer_len = leaf_count;
+    while layer_len > 1 {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, node) = nodes[i];
+            let sibling = index ^ 1;
+            let parent = if i + 1 < nodes.len() && nodes[i + 1].0 == sibling {
+                i += 1;
+                hash_node(&node, &nodes[i].1)
+            } else if sibling < layer_len {
+                hash_node(&node, proof.next()?)
+            } else {
+                node
+            };
+            parents.push((index / 2, parent));
+            i += 1;
+        }
+        nodes = parents;
+        layer_len = layer_len.div_ceil(2);
+    }
+
+    if proof.next().is_some() {
+        return None;
+    }
+    Some(nodes[0].1)
+}
+
+// Проверяет мультидоказательство: leaves - пары (индекс листа, лист)
+pub fn verify_merkle_multiproof(
+    version: MerkleLeafVersion,
+    leaf_count: u32,
+    leaves: &[(u32, [u8; 32])],
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    let hash_node = match version {
+        MerkleLeafVersion::V1 => hash_pair,
+        MerkleLeafVersion::V2 | MerkleLeafVersion::Allowance => hash_pair_v2,
+    };
+    multiproof_root(leaf_count, leaves, proof, hash_node) == Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Дерево как у merkletreejs с sortPairs: сортированные листья, непарный узел поднимается
+    fn build_layers(mut leaves: Vec<[u8; 32]>, hash_node: fn(&[u8; 32], &[u8; 32]) -> [u8; 32]) -> Vec<Vec<[u8; 32]>> {
+        leaves.sort();
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| if pair.len() == 2 { hash_node(&pair[0], &pair[1]) } else { pair[0] })
+                .collect();
+            layers.push(next);
+        }
+        layers
+    }
+
+    fn proof(layers: &[Vec<[u8; 32]>], leaf: &[u8; 32]) -> Vec<[u8; 32]> {
+        let mut index = layers[0].binary_search(leaf).unwrap();
+        let mut proof = Vec::new();
+        for layer in &layers[..layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+
+    fn wallets(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn v1_proofs_verify_for_every_leaf() {
+        for count in 1..=9 {
+            let leaves: Vec<_> = wallets(count)
+                .iter()
+                .enumerate()
+                .map(|(i, wallet)| wallet_nft_leaf(wallet, i as u16))
+                .collect();
+            let layers = build_layers(leaves.clone(), hash_pair);
+            let root = layers.last().unwrap()[0];
+            for leaf in &leaves {
+                assert!(verify_merkle_proof(*leaf, &proof(&layers, leaf), root), "{} leaves", count);
+            }
+        }
+    }
+
+    #[test]
+    fn v2_proofs_verify_for_every_leaf() {
+        let program_id = Pubkey::new_unique();
+        for count in 1..=9 {
+            let wallets = wallets(count);
+            let leaves: Vec<_> = wallets
+                .iter()
+                .enumerate()
+                .map(|(i, wallet)| wallet_nft_leaf_v2(&program_id, 3, wallet, i as u16))
+                .collect();
+            let layers = build_layers(leaves.clone(), hash_pair_v2);
+            let root = layers.last().unwrap()[0];
+            for (i, (wallet, leaf)) in wallets.iter().zip(&leaves).enumerate() {
+                let proof = proof(&layers, leaf);
+                assert!(verify_merkle_proof_v2(*leaf, &proof, root), "{} leaves", count);
+                assert!(verify_wallet_nft(MerkleLeafVersion::V2, &program_id, 3, wallet, i as u16, &proof, root));
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_is_rejected() {
+        let wallets = wallets(5);
+        let leaves: Vec<_> = wallets.iter().map(|wallet| wallet_nft_leaf(wallet, 1)).collect();
+        let layers = build_layers(leaves.clone(), hash_pair);
+        let root = layers.last().unwrap()[0];
+
+        let proof = proof(&layers, &leaves[2]);
+        let mut tampered = leaves[2];
+        tampered[0] ^= 1;
+        assert!(!verify_merkle_proof(tampered, &proof, root));
+        // Тот же кошелек с другим NFTnumber
+        assert!(!verify_merkle_proof(wallet_nft_leaf(&wallets[2], 2), &proof, root));
+        assert!(!verify_wallet_nft(MerkleLeafVersion::V1, &Pubkey::default(), 0, &wallets[2], 2, &proof, root));
+    }
+
+    #[test]
+    fn v2_proof_does_not_move_between_rounds() {
+        let program_id = Pubkey::new_unique();
+        let wallets = wallets(3);
+        let leaves: Vec<_> = wallets.iter().map(|wallet| wallet_nft_leaf_v2(&program_id, 1, wallet, 7)).collect();
+        let layers = build_layers(leaves.clone(), hash_pair_v2);
+        let root = layers.last().unwrap()[0];
+
+        let proof = proof(&layers, &leaves[0]);
+        assert!(verify_wallet_nft(MerkleLeafVersion::V2, &program_id, 1, &wallets[0], 7, &proof, root));
+        assert!(!verify_wallet_nft(MerkleLeafVersion::V2, &program_id, 2, &wallets[0], 7, &proof, root));
+        // И не проверяется как V1
+        assert!(!verify_wallet_nft(MerkleLeafVersion::V1, &program_id, 1, &wallets[0], 7, &proof, root));
+    }
+
+    // Ожидаемые хеши посчитаны по кодировке allowanceLeaf из tests/instruction_62_test.ts:
+    // программа [1; 32], раунд 3, кошелек [2; 32]
+    #[test]
+    fn allowance_leaf_matches_ts_encoding() {
+        let program_id = Pubkey::new_from_array([1; 32]);
+        let wallet = Pubkey::new_from_array([2; 32]);
+        assert_eq!(
+            allowance_leaf(&program_id, 3, &wallet, &[7, 300]),
+            Some([
+                0x9e, 0x36, 0x3b, 0x54, 0xc7, 0x34, 0xd0, 0xff, 0x9a, 0x10, 0x5a, 0x8b, 0xcc, 0x27, 0x67, 0x21,
+                0xb8, 0x85, 0xac, 0xfb, 0x7f, 0x21, 0xc1, 0x3e, 0x2c, 0x7f, 0xb4, 0x41, 0x7b, 0xe9, 0x0a, 0xa7,
+            ])
+        );
+        // Лист из одного NFTnumber отличается от листа V2 полем count
+        assert_eq!(
+            allowance_leaf(&program_id, 3, &wallet, &[7]),
+            Some([
+                0xc8, 0x8d, 0xba, 0x70, 0xef, 0x85, 0x13, 0x23, 0x29, 0x10, 0x91, 0x52, 0xff, 0x04, 0x60, 0x07,
+                0x55, 0xf8, 0xa8, 0x61, 0x3a, 0xe2, 0x24, 0xed, 0x77, 0xb4, 0x8d, 0x10, 0x76, 0x94, 0xac, 0x9a,
+            ])
+        );
+        assert_ne!(allowance_leaf(&program_id, 3, &wallet, &[7]), Some(wallet_nft_leaf_v2(&program_id, 3, &wallet, 7)));
+    }
+
+    #[test]
+    fn allowance_leaf_rejects_count_above_u16() {
+        let program_id = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let numbers = vec![1u16; u16::MAX as usize + 1];
+        assert!(allowance_leaf(&program_id, 0, &wallet, &numbers[..u16::MAX as usize]).is_some());
+        assert_eq!(allowance_leaf(&program_id, 0, &wallet, &numbers), None);
+    }
+}
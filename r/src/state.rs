@@ -0,0 +1,841 @@
+// Аккаунты состояния программы (PDA с конфигурацией)
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::{ShankAccount, ShankType};
+use solana_program::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::RError;
+
+// Сид PDA конфигурации раунда: ["round", round.to_le_bytes()]
+pub const ROUND_CONFIG_SEED: &[u8] = b"round";
+
+// Сид PDA записи о минте: ["minted", round.to_le_bytes(), wallet]
+pub const MINT_RECORD_SEED: &[u8] = b"minted";
+
+// Сид PDA битовой карты минтов раунда: ["claims", round.to_le_bytes()]
+pub const CLAIM_BITMAP_SEED: &[u8] = b"claims";
+
+// Сид PDA лимита минта кошелька: ["allowance", round.to_le_bytes(), wallet]
+pub const ALLOWANCE_RECORD_SEED: &[u8] = b"allowance";
+
+// Максимальная емкость битовой карты: все значения NFTnumber (u16)
+pub const MAX_CLAIM_BITMAP_CAPACITY: u32 = 1 << 16;
+
+// Области паузы (битовая маска в ProgramConfig.paused и RoundConfig.paused)
+pub const PAUSE_MINT: u8 = 1 << 0;
+pub const PAUSE_UPDATE: u8 = 1 << 1;
+pub const PAUSE_WITHDRAW: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_MINT | PAUSE_UPDATE | PAUSE_WITHDRAW;
+
+// Проверяет, что область не поставлена на паузу
+pub fn assert_not_paused(paused: u8, scope: u8) -> Result<(), ProgramError> {
+    if paused & scope & PAUSE_MINT != 0 {
+        msg!("Minting is paused");
+        return Err(RError::MintPaused.into());
+    }
+    if paused & scope & PAUSE_UPDATE != 0 {
+        msg!("Metadata updates are paused");
+        return Err(RError::UpdatePaused.into());
+    }
+    if paused & scope & PAUSE_WITHDRAW != 0 {
+        msg!("Withdrawals are paused");
+        return Err(RError::WithdrawPaused.into());
+    }
+    Ok(())
+}
+
+// Сид PDA программы: ["mint_authority"]. Mint/update authority и creator NFT,
+// на него же поступают роялти
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
+// Сид PDA казны: ["treasury"]. Системный аккаунт без данных, куда поступает
+// оплата минта; вывести средства может только администратор (инструкция 42)
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+pub fn find_treasury_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], program_id)
+}
+
+// Проверяет адрес PDA казны и возвращает его bump
+pub fn assert_treasury(account: &AccountInfo, program_id: &Pubkey) -> Result<u8, ProgramError> {
+    let (expected_address, bump) = find_treasury_address(program_id);
+    if account.key != &expected_address {
+        msg!("Invalid treasury account address");
+        msg!("Expected: {}", expected_address);
+        msg!("Received: {}", account.key);
+        return Err(RError::InvalidTreasuryAddress.into());
+    }
+    Ok(bump)
+}
+
+// Проверяет, что аккаунт - PDA программы или казна, с которых выводятся средства
+// (инструкции 42 и 58), и возвращает его сид и bump
+pub fn assert_withdraw_source(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<(&'static [u8], u8), ProgramError> {
+    let (mint_authority, mint_authority_bump) =
+        Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], program_id);
+    if account.key == &mint_authority {
+        return Ok((MINT_AUTHORITY_SEED, mint_authority_bump));
+    }
+    let (treasury, treasury_bump) = find_treasury_address(program_id);
+    if account.key == &treasury {
+        return Ok((TREASURY_SEED, treasury_bump));
+    }
+    msg!("Invalid withdraw source provided");
+    msg!("Expected: {} or {}", mint_authority, treasury);
+    msg!("Received: {}", account.key);
+    Err(RError::InvalidWithdrawSource.into())
+}
+
+// Проверяет, что аккаунт - программа Token Metadata (CPI UpdateV1 и разбор метаданных)
+pub fn assert_token_metadata_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    if account.key != &mpl_token_metadata::ID {
+        msg!("Invalid Token Metadata program provided");
+        msg!("Expected: {}", mpl_token_metadata::ID);
+        msg!("Received: {}", account.key);
+        return Err(RError::InvalidTokenMetadataProgram.into());
+    }
+    Ok(())
+}
+
+// Сид PDA распределения выручки: ["split"]
+pub const SPLIT_CONFIG_SEED: &[u8] = b"split";
+
+// Максимальное количество получателей в распределении выручки
+pub const MAX_SPLIT_RECIPIENTS: usize = 10;
+
+// Сумма долей получателей в базисных пунктах (100%)
+pub const SPLIT_TOTAL_BASIS_POINTS: u16 = 10000;
+
+// Сид PDA конфигурации программы: ["config"]
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"config";
+
+// Ограничения длины строк (совпадают с ограничениями Token Metadata)
+pub const MAX_NAME_TEMPLATE_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_BASE_LENGTH: usize = 200;
+
+// Плейсхолдер номера NFT в шаблонах названия и URI раунда
+pub const NFT_NUMBER_PLACEHOLDER: &str = "{nft_number}";
+// Плейсхолдер номера раунда (как в аргументах инструкций) в шаблоне названия
+pub const ROUND_PLACEHOLDER: &str = "{round}";
+
+// Максимальное количество создателей NFT (ограничение Token Metadata)
+pub const MAX_CREATORS: usize = 5;
+
+// Запас вычислительных единиц на один UpdateV1 в пакетном обновлении (инструкция 59)
+pub const BATCH_UPDATE_COMPUTE_UNITS: u64 = 60_000;
+
+// Запас вычислительных единиц на минт одного pNFT по мультидоказательству (инструкция 61)
+pub const MULTI_MINT_COMPUTE_UNITS: u64 = 200_000;
+
+// Максимальная комиссия с продаж в базисных пунктах (100%)
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10000;
+
+// Максимальная доля сжигания оплаты токеном в базисных пунктах (100%)
+pub const MAX_BURN_BASIS_POINTS: u16 = 10000;
+
+// Дискриминатор аккаунтов программы (первый байт данных)
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Uninitialized,
+    RoundConfig,
+    ProgramConfig,
+    MintRecordV2,
+    ClaimBitmap,
+    SplitConfig,
+    AllowanceRecord,
+}
+
+// Формат листьев и узлов Merkle-дерева раунда (см. merkle)
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MerkleLeafVersion {
+    // sha256(wallet || NFTnumber LE), узлы sha256(min || max); старые аккаунты
+    #[default]
+    V1,
+    // sha256(0x00 || program_id || round || wallet || NFTnumber), узлы
+    // sha256(0x01 || min || max): доказательство привязано к программе и раунду
+    V2,
+    // Лист на кошелек с лимитом минта: sha256(0x00 || program_id || round || wallet ||
+    // count || NFTnumber * count), узлы как в V2. Минт - инструкцией 62
+    Allowance,
+}
+
+// Старая запись о минте (V1): только адрес минта NFT, без дискриминатора.
+// Новые записи не создаются, существующие переводятся в V2 инструкцией 52
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintRecordV1 {
+    pub mint: Pubkey,
+}
+
+impl MintRecordV1 {
+    pub const LEN: usize = 32; // mint
+}
+
+// Запись о минте кошелька в раунде (V2): PDA ["minted", round.to_le_bytes(), wallet]
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct MintRecordV2 {
+    pub key: Key,
+    pub version: u8,
+    pub round: u64,
+    pub mint: Pubkey,
+    // 0 - номер неизвестен (раунды без NFTnumber в листе, записи из V1)
+    pub nft_number: u16,
+    pub minter: Pubkey,
+    // 0 - время минта неизвестно (записи из V1)
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    pub bump: u8,
+}
+
+impl MintRecordV2 {
+    pub const VERSION: u8 = 2;
+
+    pub const LEN: usize = 1 // key
+        + 1 // version
+        + 8 // round
+        + 32 // mint
+        + 2 // nft_number
+        + 32 // minter
+        + 8 // slot
+        + 8 // unix_timestamp
+        + 1; // bump
+
+    pub fn find_address(round: u64, wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[MINT_RECORD_SEED, &round.to_le_bytes(), wallet.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[..]).map_err(|_| {
+            msg!("Failed to serialize mint record");
+            ProgramError::AccountDataTooSmall
+        })
+    }
+}
+
+// Битовая карта минтов раунда: заголовок, за которым идут capacity бит (по NFTnumber).
+// Если PDA создан для раунда, инструкция 45 отмечает бит вместо создания записи о минте.
+// Биты читаются и пишутся прямо в данных аккаунта, без десериализации всей карты
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct ClaimBitmap {
+    pub key: Key,
+    pub round: u64,
+    pub capacity: u32,
+    pub bump: u8,
+}
+
+impl ClaimBitmap {
+    pub const HEADER_LEN: usize = 1 // key
+        + 8 // round
+        + 4 // capacity
+        + 1; // bump
+
+    // Полный размер аккаунта: заголовок и биты, округленные до байта
+    pub fn len(capacity: u32) -> usize {
+        Self::HEADER_LEN + (capacity as usize).div_ceil(8)
+    }
+
+    pub fn find_address(round: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CLAIM_BITMAP_SEED, &round.to_le_bytes()], program_id)
+    }
+
+    // Загружает заголовок карты, если она создана для раунда.
+    // None - карты нет, раунд использует записи о минте
+    pub fn load(account: &AccountInfo, round: u64, program_id: &Pubkey) -> Result<Option<Self>, ProgramError> {
+        let (expected_address, _) = Self::find_address(round, program_id);
+        if account.key != &expected_address {
+            msg!("Invalid claim bitmap account address");
+            msg!("Expected: {}", expected_address);
+            msg!("Received: {}", account.key);
+            return Err(RError::InvalidClaimBitmapAddress.into());
+        }
+
+        if account.data_is_empty() || account.owner != program_id {
+            return Ok(None);
+        }
+
+        let data = account.try_borrow_data()?;
+        let header = Self::deserialize(&mut &data[..Self::HEADER_LEN]).map_err(|_| {
+            msg!("Failed to deserialize claim bitmap");
+            ProgramError::InvalidAccountData
+        })?;
+
+        if header.key != Key::ClaimBitmap || header.round != round {
+            msg!("Claim bitmap account is not initialized for round {}", round);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Some(header))
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[..Self::HEADER_LEN]).map_err(|_| {
+            msg!("Failed to serialize claim bitmap");
+            ProgramError::AccountDataTooSmall
+        })
+    }
+
+    // Позиция бита в данных аккаунта: (индекс байта, маска)
+    fn bit_position(&self, index: u16) -> Result<(usize, u8), ProgramError> {
+        if u32::from(index) >= self.capacity {
+            msg!("NFTnumber {} is out of claim bitmap range (capacity {})", index, self.capacity);
+            return Err(RError::NftNumberOutOfRange.into());
+        }
+        let index = index as usize;
+        Ok((Self::HEADER_LEN + index / 8, 1 << (index % 8)))
+    }
+
+    pub fn is_claimed(&self, account: &AccountInfo, index: u16) -> Result<bool, ProgramError> {
+        let (byte, mask) = self.bit_position(index)?;
+        let data = account.try_borrow_data()?;
+        Ok(data[byte] & mask != 0)
+    }
+
+    // Отмечает NFTnumber как заминченный; повторная отметка - ошибка
+    pub fn set_claimed(&self, account: &AccountInfo, index: u16) -> Result<(), ProgramError> {
+        let (byte, mask) = self.bit_position(index)?;
+        let mut data = account.try_borrow_mut_data()?;
+        if data[byte] & mask != 0 {
+            msg!("NFTnumber {} has already been minted in round {}", index, self.round);
+            return Err(RError::NftNumberAlreadyClaimed.into());
+        }
+        data[byte] |= mask;
+        Ok(())
+    }
+}
+
+// Конфигурация программы: администратор и предложенный новый администратор
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct ProgramConfig {
+    pub key: Key,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub bump: u8,
+    // Глобальная пауза (маска PAUSE_*); добавлено после bump, см. load
+    pub paused: u8,
+    // Коллекция, созданная инструкцией 39; Pubkey::default() - еще не создана
+    pub collection_mint: Pubkey,
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 1 // key
+        + 32 // admin
+        + 1 + 32 // pending_admin
+        + 1 // bump
+        + 1 // paused
+        + 32; // collection_mint
+
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], program_id)
+    }
+
+    // Загружает конфигурацию программы, проверяя владельца, адрес PDA и дискриминатор
+    pub fn load(account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != program_id {
+            msg!("Program config account does not belong to the program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (expected_address, _) = Self::find_address(program_id);
+        if account.key != &expected_address {
+            msg!("Invalid program config account address");
+            msg!("Expected: {}", expected_address);
+            msg!("Received: {}", account.key);
+            return Err(RError::InvalidProgramConfigAddress.into());
+        }
+
+        // Аккаунты, созданные до добавления новых полей, короче LEN:
+        // недостающие поля читаются как 0
+        let mut data = account.try_borrow_data()?.to_vec();
+        if data.len() < Self::LEN {
+            data.resize(Self::LEN, 0);
+        }
+        let config = Self::deserialize(&mut &data[..]).map_err(|_| {
+            msg!("Failed to deserialize program config");
+            ProgramError::InvalidAccountData
+        })?;
+
+        if config.key != Key::ProgramConfig {
+            msg!("Program config account is not initialized");
+            return Err(RError::ProgramConfigNotInitialized.into());
+        }
+
+        Ok(config)
+    }
+
+    // Аккаунты, созданные до добавления новых полей, короче LEN: нулевой хвост
+    // (поля по умолчанию) не записывается, иначе аккаунт нужно сначала увеличить
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let bytes = borsh::to_vec(self)?;
+        let mut data = account.try_borrow_mut_data()?;
+        let len = data.len().min(bytes.len());
+        if bytes[len..].iter().any(|byte| *byte != 0) {
+            msg!("Program config account is too small: {} < {}", data.len(), bytes.len());
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        data[..len].copy_from_slice(&bytes[..len]);
+        Ok(())
+    }
+
+    // Проверяет, что admin подписал транзакцию и совпадает с администратором программы
+    pub fn assert_admin(
+        program_config_account: &AccountInfo,
+        admin: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        if !admin.is_signer {
+            msg!("Admin must be a signer");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let config = Self::load(program_config_account, program_id)?;
+        if admin.key != &config.admin {
+            msg!("Signer is not the program admin");
+            msg!("Expected: {}", config.admin);
+            msg!("Received: {}", admin.key);
+            return Err(RError::Unauthorized.into());
+        }
+
+        Ok(config)
+    }
+
+    // Проверяет, что collection_mint - коллекция программы (инструкция 39)
+    pub fn assert_collection_mint(&self, collection_mint: &AccountInfo) -> Result<(), ProgramError> {
+        if self.collection_mint == Pubkey::default() {
+            msg!("Collection has not been created yet");
+            return Err(RError::CollectionNotConfigured.into());
+        }
+        if collection_mint.key != &self.collection_mint {
+            msg!("Invalid collection mint address provided");
+            msg!("Expected: {}", self.collection_mint);
+            msg!("Received: {}", collection_mint.key);
+            return Err(RError::InvalidCollectionMint.into());
+        }
+        Ok(())
+    }
+}
+
+// Конфигурация раунда: корень Merkle и параметры минта
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct RoundConfig {
+    pub key: Key,
+    pub round: u64,
+    pub merkle_root: [u8; 32],
+    // Коллекция NFT раунда; Pubkey::default() - коллекция программы
+    pub collection_mint: Pubkey,
+    // Шаблон названия, например "Name #{nft_number}"; {round} заменяется номером
+    // раунда (обязателен для раунда с раскрытием)
+    pub name_template: String,
+    pub symbol: String,
+    // Базовый URI: NFT получает {uri_base}/{nft_number}.json, либо uri_base
+    // с подстановкой, если в нем есть {nft_number}
+    pub uri_base: String,
+    pub seller_fee_basis_points: u16,
+    pub bump: u8,
+    // Поля ниже добавлены после bump: в старых аккаунтах они читаются как 0
+    // Окно минта (unix timestamp); 0 - без ограничения. Начало после наступления
+    // не меняется (инструкция 48)
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    // Пауза раунда (маска PAUSE_*)
+    pub paused: u8,
+    // Цена минта в лампортах (оплата в казну); 0 - бесплатно
+    pub price_lamports: u64,
+    // Цена минта в SPL / Token-2022 токене: переводится в хранилище (ATA казны),
+    // burn_basis_points от суммы сжигается; комиссию за перевод (TransferFee)
+    // плательщик доплачивает сверх цены. price_amount = 0 - без оплаты токеном
+    pub price_mint: Pubkey,
+    pub price_amount: u64,
+    pub burn_basis_points: u16,
+    // Программа токенов для минта NFT раунда: SPL Token или Token-2022;
+    // Pubkey::default() (старые аккаунты) - SPL Token
+    pub token_program: Pubkey,
+    // Корень Merkle манифеста отложенного раскрытия (NFTnumber -> URI), см.
+    // инструкцию 60; нули - раскрытие не настроено. Задается до начала минта
+    pub reveal_root: [u8; 32],
+    // Формат листьев корня merkle_root
+    pub leaf_version: MerkleLeafVersion,
+}
+
+impl RoundConfig {
+    // Размер аккаунта с учетом максимальной длины строк
+    pub const LEN: usize = 1 // key
+        + 8 // round
+        + 32 // merkle_root
+        + 32 // collection_mint
+        + 4 + MAX_NAME_TEMPLATE_LENGTH
+        + 4 + MAX_SYMBOL_LENGTH
+        + 4 + MAX_URI_BASE_LENGTH
+        + 2 // seller_fee_basis_points
+        + 1 // bump
+        + 8 // start_timestamp
+        + 8 // end_timestamp
+        + 1 // paused
+        + 8 // price_lamports
+        + 32 // price_mint
+        + 8 // price_amount
+        + 2 // burn_basis_points
+        + 32 // token_program
+        + 32 // reveal_root
+        + 1; // leaf_version
+
+    pub fn find_address(round: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ROUND_CONFIG_SEED, &round.to_le_bytes()], program_id)
+    }
+
+    // Загружает конфигурацию раунда, проверяя владельца, адрес PDA и дискриминатор
+    pub fn load(account: &AccountInfo, round: u64, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != program_id {
+            msg!("Round config account does not belong to the program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (expected_address, _) = Self::find_address(round, program_id);
+        if account.key != &expected_address {
+            msg!("Invalid round config account address");
+            msg!("Expected: {}", expected_address);
+            msg!("Received: {}", account.key);
+            return Err(RError::InvalidRoundConfigAddress.into());
+        }
+
+        // Аккаунты, созданные до добавления новых полей, могут быть короче LEN:
+        // недостающие поля читаются как 0, инструкция 48 увеличивает аккаунт до LEN
+        let mut data = account.try_borrow_data()?.to_vec();
+        if data.len() < Self::LEN {
+            data.resize(Self::LEN, 0);
+        }
+        let config = Self::deserialize(&mut &data[..]).map_err(|_| {
+            msg!("Failed to deserialize round config");
+            ProgramError::InvalidAccountData
+        })?;
+
+        if config.key != Key::RoundConfig || config.round != round {
+            msg!("Round config account is not initialized for round {}", round);
+            return Err(RError::RoundConfigNotInitialized.into());
+        }
+
+        Ok(config)
+    }
+
+    // Сохраняет конфигурацию в данные аккаунта (хвост остается нулевым). Старый
+    // аккаунт короче LEN может не вместить новые поля: его увеличивает инструкция 48
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[..]).map_err(|_| {
+            msg!("Failed to serialize round config, account size {} < {}: update the round config first", data.len(), Self::LEN);
+            ProgramError::AccountDataTooSmall
+        })
+    }
+
+    // Проверяет ограничения на строки и комиссию
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.name_template.len() > MAX_NAME_TEMPLATE_LENGTH {
+            msg!("Name template is too long: {} > {}", self.name_template.len(), MAX_NAME_TEMPLATE_LENGTH);
+            return Err(RError::NameTooLong.into());
+        }
+        if self.nft_name(u16::MAX).len() > MAX_NAME_TEMPLATE_LENGTH {
+            msg!("Name is too long: {} > {}", self.nft_name(u16::MAX).len(), MAX_NAME_TEMPLATE_LENGTH);
+            return Err(RError::NameTooLong.into());
+        }
+        if self.symbol.len() > MAX_SYMBOL_LENGTH {
+            msg!("Symbol is too long: {} > {}", self.symbol.len(), MAX_SYMBOL_LENGTH);
+            return Err(RError::SymbolTooLong.into());
+        }
+        if self.uri_base.len() > MAX_URI_BASE_LENGTH {
+            msg!("URI base is too long: {} > {}", self.uri_base.len(), MAX_URI_BASE_LENGTH);
+            return Err(RError::UriTooLong.into());
+        }
+        // URI с самым длинным номером тоже должен уложиться в ограничение Token Metadata
+        if self.nft_uri(u16::MAX).len() > MAX_URI_BASE_LENGTH {
+            msg!("URI is too long: {} > {}", self.nft_uri(u16::MAX).len(), MAX_URI_BASE_LENGTH);
+            return Err(RError::UriTooLong.into());
+        }
+        if self.seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+            msg!("Seller fee basis points must be <= {}", MAX_SELLER_FEE_BASIS_POINTS);
+            return Err(RError::InvalidSellerFeeBasisPoints.into());
+        }
+        if self.price_amount > 0 && self.price_mint == Pubkey::default() {
+            msg!("Token price is set without a payment mint");
+            return Err(RError::InvalidPaymentMint.into());
+        }
+        if self.burn_basis_points > MAX_BURN_BASIS_POINTS {
+            msg!("Burn basis points must be <= {}", MAX_BURN_BASIS_POINTS);
+            return Err(RError::InvalidBurnBasisPoints.into());
+        }
+        if self.token_program != Pubkey::default()
+            && self.token_program != spl_token::ID
+            && self.token_program != spl_token_2022::ID
+        {
+            msg!("Token program must be SPL Token or Token-2022, got {}", self.token_program);
+            return Err(RError::InvalidTokenProgram.into());
+        }
+        if self.start_timestamp < 0 || self.end_timestamp < 0 {
+            msg!("Mint window timestamps must not be negative");
+            return Err(RError::InvalidMintWindow.into());
+        }
+        if self.start_timestamp != 0 && self.end_timestamp != 0 && self.end_timestamp <= self.start_timestamp {
+            msg!("Mint window end must be after start");
+            return Err(RError::InvalidMintWindow.into());
+        }
+        // Инструкция 60 находит NFT по названию: номер NFT и раунд должны в него
+        // входить, иначе NFT другого раунда с той же коллекцией и шаблоном можно
+        // было бы раскрыть по манифесту этого раунда
+        if self.reveal_root != [0u8; 32]
+            && !(self.name_template.contains(NFT_NUMBER_PLACEHOLDER)
+                && self.name_template.contains(ROUND_PLACEHOLDER))
+        {
+            msg!("Name template must contain {} and {} for reveal", NFT_NUMBER_PLACEHOLDER, ROUND_PLACEHOLDER);
+            return Err(RError::InvalidRevealConfig.into());
+        }
+        Ok(())
+    }
+
+    // Название NFT: name_template с подстановкой {nft_number} и {round}
+    pub fn nft_name(&self, nft_number: u16) -> String {
+        self.name_template
+            .replace(NFT_NUMBER_PLACEHOLDER, &nft_number.to_string())
+            .replace(ROUND_PLACEHOLDER, &self.round.to_string())
+    }
+
+    // URI NFT: uri_base с подстановкой {nft_number}; без плейсхолдера - {uri_base}/{nft_number}.json
+    pub fn nft_uri(&self, nft_number: u16) -> String {
+        if self.uri_base.contains(NFT_NUMBER_PLACEHOLDER) {
+            self.uri_base.replace(NFT_NUMBER_PLACEHOLDER, &nft_number.to_string())
+        } else {
+            format!("{}/{}.json", self.uri_base.trim_end_matches('/'), nft_number)
+        }
+    }
+
+    // Коллекция NFT раунда: своя или коллекция программы (инструкция 39)
+    pub fn collection(&self, program_config: &ProgramConfig) -> Pubkey {
+        if self.collection_mint == Pubkey::default() {
+            program_config.collection_mint
+        } else {
+            self.collection_mint
+        }
+    }
+
+    // Коллекция раунда; Pubkey::default() - коллекция программы (инструкция 39)
+    pub fn assert_collection_mint(
+        &self,
+        program_config: &ProgramConfig,
+        collection_mint: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if self.collection_mint == Pubkey::default() {
+            return program_config.assert_collection_mint(collection_mint);
+        }
+        if collection_mint.key != &self.collection_mint {
+            msg!("Invalid collection mint address provided");
+            msg!("Expected: {}", self.collection_mint);
+            msg!("Received: {}", collection_mint.key);
+            return Err(RError::InvalidCollectionMint.into());
+        }
+        Ok(())
+    }
+
+    // Программа токенов для минта NFT раунда
+    pub fn token_program_id(&self) -> Pubkey {
+        if self.token_program == Pubkey::default() {
+            spl_token::ID
+        } else {
+            self.token_program
+        }
+    }
+
+    // Раунд начался, если минт уже открыт: без start_timestamp - сразу
+    pub fn has_started(&self, unix_timestamp: i64) -> bool {
+        self.start_timestamp == 0 || unix_timestamp >= self.start_timestamp
+    }
+
+    // Проверяет, что текущее время попадает в окно минта раунда
+    pub fn assert_mint_window(&self, unix_timestamp: i64) -> Result<(), ProgramError> {
+        if self.start_timestamp != 0 && unix_timestamp < self.start_timestamp {
+            msg!("Round {} has not started yet: now {}, start {}", self.round, unix_timestamp, self.start_timestamp);
+            return Err(RError::RoundNotStarted.into());
+        }
+        if self.end_timestamp != 0 && unix_timestamp >= self.end_timestamp {
+            msg!("Round {} has ended: now {}, end {}", self.round, unix_timestamp, self.end_timestamp);
+            return Err(RError::RoundEnded.into());
+        }
+        Ok(())
+    }
+}
+
+// Получатель доли выручки
+#[derive(BorshSerialize, BorshDeserialize, ShankType, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitRecipient {
+    pub address: Pubkey,
+    pub basis_points: u16,
+}
+
+// Распределение выручки между кошельками команды (инструкции 57 и 58)
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct SplitConfig {
+    pub key: Key,
+    pub bump: u8,
+    // Всего выплачено получателям за все распределения
+    pub total_distributed: u64,
+    // Остаток последнего распределения (округление и доли ниже минимума для
+    // ренты): остается на источнике и входит в следующее распределение
+    pub dust: u64,
+    pub recipients: Vec<SplitRecipient>,
+}
+
+impl SplitConfig {
+    // Размер аккаунта с учетом максимального количества получателей
+    pub const LEN: usize = 1 // key
+        + 1 // bump
+        + 8 // total_distributed
+        + 8 // dust
+        + 4 + MAX_SPLIT_RECIPIENTS * (32 + 2); // recipients
+
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SPLIT_CONFIG_SEED], program_id)
+    }
+
+    // Загружает распределение, проверяя владельца, адрес PDA и дискриминатор
+    pub fn load(account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != program_id {
+            msg!("Split config account does not belong to the program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (expected_address, _) = Self::find_address(program_id);
+        if account.key != &expected_address {
+            msg!("Invalid split config account address");
+            msg!("Expected: {}", expected_address);
+            msg!("Received: {}", account.key);
+            return Err(RError::InvalidSplitConfigAddress.into());
+        }
+
+        let config = Self::deserialize(&mut &account.try_borrow_data()?[..]).map_err(|_| {
+            msg!("Failed to deserialize split config");
+            ProgramError::InvalidAccountData
+        })?;
+
+        if config.key != Key::SplitConfig {
+            msg!("Split config account is not initialized");
+            return Err(RError::SplitConfigNotInitialized.into());
+        }
+
+        Ok(config)
+    }
+
+    // Сохраняет распределение в данные аккаунта (хвост остается нулевым)
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        data.fill(0);
+        self.serialize(&mut &mut data[..]).map_err(|_| {
+            msg!("Failed to serialize split config");
+            ProgramError::AccountDataTooSmall
+        })
+    }
+
+    // Проверяет список получателей: от 1 до MAX_SPLIT_RECIPIENTS разных адресов,
+    // доли больше 0 и в сумме ровно 100%
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.recipients.is_empty() || self.recipients.len() > MAX_SPLIT_RECIPIENTS {
+            msg!("Split must have between 1 and {} recipients", MAX_SPLIT_RECIPIENTS);
+            return Err(RError::InvalidSplitRecipients.into());
+        }
+        for (i, recipient) in self.recipients.iter().enumerate() {
+            if self.recipients[..i].iter().any(|other| other.address == recipient.address) {
+                msg!("Duplicate split recipient: {}", recipient.address);
+                return Err(RError::InvalidSplitRecipients.into());
+            }
+            if recipient.basis_points == 0 {
+                msg!("Split share of {} must be greater than 0", recipient.address);
+                return Err(RError::InvalidSplitShares.into());
+            }
+        }
+        let total: u32 = self.recipients.iter().map(|r| r.basis_points as u32).sum();
+        if total != SPLIT_TOTAL_BASIS_POINTS as u32 {
+            msg!("Split shares must sum to {}, got {}", SPLIT_TOTAL_BASIS_POINTS, total);
+            return Err(RError::InvalidSplitShares.into());
+        }
+        Ok(())
+    }
+}
+
+// Сколько NFT кошелек уже заминтил по своему лимиту (инструкция 62):
+// PDA ["allowance", round.to_le_bytes(), wallet]. Лимит и NFTnumber кошелька
+// заданы листом дерева раунда, запись хранит только счетчик
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount, Debug, Clone, PartialEq, Eq)]
+pub struct AllowanceRecord {
+    pub key: Key,
+    pub round: u64,
+    pub wallet: Pubkey,
+    pub minted: u16,
+    pub bump: u8,
+}
+
+impl AllowanceRecord {
+    pub const LEN: usize = 1 // key
+        + 8 // round
+        + 32 // wallet
+        + 2 // minted
+        + 1; // bump
+
+    pub fn find_address(round: u64, wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[ALLOWANCE_RECORD_SEED, &round.to_le_bytes(), wallet.as_ref()],
+            program_id,
+        )
+    }
+
+    // Загружает запись, проверяя адрес PDA, владельца и дискриминатор;
+    // None - запись еще не создана (кошелек ничего не минтил)
+    pub fn load(
+        account: &AccountInfo,
+        round: u64,
+        wallet: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Option<Self>, ProgramError> {
+        let (expected_address, _) = Self::find_address(round, wallet, program_id);
+        if account.key != &expected_address {
+            msg!("Invalid allowance record account address");
+            msg!("Expected: {}", expected_address);
+            msg!("Received: {}", account.key);
+            return Err(RError::InvalidAllowanceRecordAddress.into());
+        }
+
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        if account.owner != program_id {
+            msg!("Allowance record account does not belong to the program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let record = Self::deserialize(&mut &account.try_borrow_data()?[..]).map_err(|_| {
+            msg!("Failed to deserialize allowance record");
+            ProgramError::InvalidAccountData
+        })?;
+        if record.key != Key::AllowanceRecord {
+            msg!("Allowance record account is not initialized");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Some(record))
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[..]).map_err(|_| {
+            msg!("Failed to serialize allowance record");
+            ProgramError::AccountDataTooSmall
+        })
+    }
+}
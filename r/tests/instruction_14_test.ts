@@ -27,6 +27,9 @@ const TARGET_MINT = new PublicKey('E2i6VKGsjXXbSXJUL2y6yEkJrHQZwSke7r9yuCQScrun'
 // Номер раунда (5 для раунда 6, так как нумерация с 0)
 const ROUND_NUMBER = 5;
 
+// Номер NFT в раунде
+const NFT_NUMBER = 1;
+
 describe('Instruction 14', function() {
   // Увеличиваем таймаут до 60 секунд
   this.timeout(60000);
@@ -59,13 +62,22 @@ describe('Instruction 14', function() {
     );
     console.log('Mint Record PDA:', mintRecordAccount.toBase58());
 
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
     try {
       // Создаем буфер данных для инструкции
-      const instructionData = Buffer.alloc(41); // 1 байт для номера инструкции + 8 байт для раунда + 32 байта для минта
+      const instructionData = Buffer.alloc(44); // номер инструкции + версия данных + 8 байт для раунда + 32 байта для минта + 2 байта для NFTnumber
       instructionData.writeUInt8(14, 0); // Номер инструкции
-      instructionData.writeBigUInt64LE(BigInt(ROUND_NUMBER), 1); // Номер раунда как u64
+      instructionData.writeUInt8(1, 1); // Версия данных: 1 - ManualMintRecordArgsV2, с NFTnumber
+      instructionData.writeBigUInt64LE(BigInt(ROUND_NUMBER), 2); // Номер раунда как u64
       const mintBytes = TARGET_MINT.toBuffer();
-      instructionData.set(mintBytes, 9); // Адрес минта
+      instructionData.set(mintBytes, 10); // Адрес минта
+      instructionData.writeUInt16LE(NFT_NUMBER, 42); // NFTnumber как u16
       
       console.log('Создаем инструкцию...');
       const instruction = new TransactionInstruction({
@@ -74,6 +86,8 @@ describe('Instruction 14', function() {
           { pubkey: mintRecordAccount, isSigner: false, isWritable: true },
           { pubkey: payer.publicKey, isSigner: true, isWritable: true },
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          { pubkey: programConfig, isSigner: false, isWritable: false },
+          { pubkey: payer.publicKey, isSigner: false, isWritable: false }, // wallet
         ],
         data: instructionData
       });
@@ -106,10 +120,13 @@ describe('Instruction 14', function() {
         console.log('Размер Mint Record аккаунта:', mintRecordInfo.data.length);
         console.log('Владелец Mint Record аккаунта:', mintRecordInfo.owner.toBase58());
         
-        // Проверяем записанный адрес минта
-        const storedMint = new PublicKey(mintRecordInfo.data.slice(0, 32));
+        // Проверяем запись V2: key (1) + version (1) + round (8) + mint (32) + nft_number (2) + ...
+        expect(mintRecordInfo.data.length).to.equal(93);
+        expect(mintRecordInfo.data[1]).to.equal(2);
+        const storedMint = new PublicKey(mintRecordInfo.data.slice(10, 42));
         console.log('Записанный адрес минта:', storedMint.toBase58());
         expect(storedMint.equals(TARGET_MINT)).to.be.true;
+        expect(mintRecordInfo.data.readUInt16LE(42)).to.equal(NFT_NUMBER);
       }
 
       expect(mintRecordInfo).to.not.be.null;
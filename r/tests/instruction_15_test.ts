@@ -52,6 +52,13 @@ describe('Instruction 15 Test', function() {
     );
     console.log('Mint Record PDA:', mintRecordPDA.toBase58());
 
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
     // Проверяем, существует ли аккаунт перед удалением
     const accountInfo = await connection.getAccountInfo(mintRecordPDA);
     if (!accountInfo) {
@@ -63,15 +70,17 @@ describe('Instruction 15 Test', function() {
     console.log(`Владелец аккаунта: ${accountInfo.owner.toBase58()}`);
 
     // Создаем буфер данных для инструкции
-    const dataBuffer = Buffer.alloc(2);
-    dataBuffer[0] = 15; // Инструкция 15
-    dataBuffer[1] = roundNumber; // Используем раунд 1
+    const dataBuffer = Buffer.alloc(9);
+    dataBuffer.writeUInt8(15, 0); // Инструкция 15
+    dataBuffer.writeBigUInt64LE(BigInt(roundNumber), 1); // Номер раунда как u64
 
     // Создаем инструкцию
     const instruction = new TransactionInstruction({
       keys: [
         { pubkey: payer.publicKey, isSigner: true, isWritable: true },
         { pubkey: mintRecordPDA, isSigner: false, isWritable: true },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        { pubkey: payer.publicKey, isSigner: false, isWritable: false }, // wallet
       ],
       programId: PROGRAM_ID,
       data: dataBuffer,
@@ -1,7 +1,8 @@
 /**
- * В мэиннете он! Тест для инструкции 39: Создание NFT-коллекции стандартного токена с использованием SPL Token и чеканкой на АТА программы
- * 
+ * В мэиннете он! Тест для инструкции 39: Создание NFT-коллекции с чеканкой на АТА программы
  * 
+ * Параметры коллекции передаются в данных инструкции, минт коллекции записывается
+ * в конфигурацию программы.
  */
 import { 
   Connection, 
@@ -69,6 +70,21 @@ try {
 }
 
 const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Строка в формате Borsh: длина (u32 LE) + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
+
+// Минт коллекции идет после key (1), admin (32), pending_admin (Option), bump (1) и paused (1)
+function readCollectionMint(data: Buffer): PublicKey {
+  const bumpOffset = data[33] === 0 ? 34 : 66;
+  return new PublicKey(data.subarray(bumpOffset + 2, bumpOffset + 34));
+}
+
 console.log('Token Program ID в тесте:', TOKEN_PROGRAM_ID.toBase58());
 console.log('Token Program ID в виде массива байтов:', Array.from(TOKEN_PROGRAM_ID.toBytes()));
 console.log('ID программы:', PROGRAM_ID.toBase58());
@@ -148,9 +164,29 @@ describe('Instruction 39', function() {
     );
     console.log('Token Record PDA:', tokenRecord.toBase58());
 
-    // Создаем буфер данных для инструкции
-    const dataBuffer = Buffer.alloc(1);
-    dataBuffer[0] = 39; // Инструкция 39
+    // Получаем PDA конфигурации программы (администратор и минт коллекции)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    // [0] - номер инструкции, далее CreateCollectionArgs в формате Borsh:
+    // name, symbol, uri, seller_fee_basis_points (u16), creators (Vec, пусто - PDA программы),
+    // collection_size (u64)
+    const feeBuffer = Buffer.alloc(2);
+    feeBuffer.writeUInt16LE(1000, 0);
+    const creatorsBuffer = Buffer.alloc(4); // пустой список создателей
+    const sizeBuffer = Buffer.alloc(8);
+    const dataBuffer = Buffer.concat([
+      Buffer.from([39]),
+      borshString('Yapster Infinity'),
+      borshString('YAP8v1'),
+      borshString('https://a.b/c.json'),
+      feeBuffer,
+      creatorsBuffer,
+      sizeBuffer,
+    ]);
 
     try {
       console.log('Создаем инструкцию...');
@@ -175,6 +211,9 @@ describe('Instruction 39', function() {
           
           // Аккаунт для rent
           { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+
+          // Конфигурация программы: проверка администратора и запись минта коллекции
+          { pubkey: programConfig, isSigner: false, isWritable: true },
         ],
         data: dataBuffer
       });
@@ -276,6 +315,11 @@ describe('Instruction 39', function() {
         expect(mintInfo.owner.equals(TOKEN_PROGRAM_ID)).to.be.true;
       }
 
+      // Минт коллекции записан в конфигурацию программы
+      const configInfo = await connection.getAccountInfo(programConfig);
+      expect(configInfo).to.not.be.null;
+      expect(readCollectionMint(configInfo!.data).toBase58()).to.equal(mint.publicKey.toBase58());
+
       console.log('Тест успешно завершен! Стандартный токен создан и отправлен на АТА программы');
 
     } catch (err: any) {
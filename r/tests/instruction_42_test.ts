@@ -1,6 +1,9 @@
 /**
- * Тест для инструкции 42: Отправка SOL с PDA программы на указанный адрес
+ * Тест для инструкции 42: Вывод SOL с PDA программы на адрес из списка аккаунтов
  * 
+ * Получатель задается через TEST_RECIPIENT (по умолчанию - кошелек плательщика).
+ * Источник - PDA mint_authority, с TEST_WITHDRAW_SOURCE=treasury - казна (оплата минта).
+ * Проверяется событие Withdraw в логах транзакции ("Program data").
  */
 import { 
   Connection, 
@@ -26,10 +29,7 @@ if (!process.env.PROGRAM_ID) {
 }
 const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
 
-// Фиксированный адрес получателя
-const RECIPIENT_ADDRESS = new PublicKey('GDi7rtknaEdvgGrm9qpXbF54ZGZMGezmXLky2VQac2c6');
 console.log('ID программы:', PROGRAM_ID.toBase58());
-console.log('Адрес получателя:', RECIPIENT_ADDRESS.toBase58());
 
 describe('Instruction 42', function() {
   // Увеличиваем таймаут до 60 секунд
@@ -40,17 +40,30 @@ describe('Instruction 42', function() {
   // Загружаем приватный ключ из .env в формате base58
   const privateKeyString = process.env.PRIVATE_KEY!;
   const payer = Keypair.fromSecretKey(bs58.decode(privateKeyString));
+  // Получатель берется из списка аккаунтов инструкции
+  const RECIPIENT_ADDRESS = process.env.TEST_RECIPIENT
+    ? new PublicKey(process.env.TEST_RECIPIENT)
+    : payer.publicKey;
+  console.log('Адрес получателя:', RECIPIENT_ADDRESS.toBase58());
   
   it('should withdraw SOL from program PDA to recipient', async function() {
     console.log('Начинаем тест отправки SOL с PDA программы (инструкция 42)');
     console.log('Адрес плательщика:', payer.publicKey.toBase58());
     
-    // Находим PDA программы
-    const [programAuthority, _bump] = PublicKey.findProgramAddressSync(
-      [Buffer.from('mint_authority')],
+    // Находим PDA-источник: mint_authority или казну
+    const sourceSeed = process.env.TEST_WITHDRAW_SOURCE === 'treasury' ? 'treasury' : 'mint_authority';
+    const [sourcePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(sourceSeed)],
       PROGRAM_ID
     );
-    console.log('Program Authority PDA:', programAuthority.toBase58());
+    console.log(`Source PDA (${sourceSeed}):`, sourcePda.toBase58());
+
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
 
     // Сумма для отправки в лампортах (0.01 SOL = 10_000_000 лампортов)
     const amountToSend = 30_000_000;
@@ -61,7 +74,7 @@ describe('Instruction 42', function() {
     console.log(`Баланс получателя до транзакции: ${recipientBalanceBefore / LAMPORTS_PER_SOL} SOL`);
 
     // Проверяем баланс PDA до транзакции
-    const pdaBalanceBefore = await connection.getBalance(programAuthority);
+    const pdaBalanceBefore = await connection.getBalance(sourcePda);
     console.log(`Баланс PDA до транзакции: ${pdaBalanceBefore / LAMPORTS_PER_SOL} SOL`);
 
     // Если у PDA недостаточно средств, отправляем немного SOL на PDA
@@ -71,7 +84,7 @@ describe('Instruction 42', function() {
       const fundPdaTx = new Transaction().add(
         SystemProgram.transfer({
           fromPubkey: payer.publicKey,
-          toPubkey: programAuthority,
+          toPubkey: sourcePda,
           lamports: 0.05 * LAMPORTS_PER_SOL
         })
       );
@@ -85,7 +98,7 @@ describe('Instruction 42', function() {
       console.log(`PDA пополнен, сигнатура: ${fundPdaSignature}`);
       
       // Проверяем обновленный баланс PDA
-      const pdaBalanceAfterFunding = await connection.getBalance(programAuthority);
+      const pdaBalanceAfterFunding = await connection.getBalance(sourcePda);
       console.log(`Баланс PDA после пополнения: ${pdaBalanceAfterFunding / LAMPORTS_PER_SOL} SOL`);
     }
 
@@ -94,16 +107,18 @@ describe('Instruction 42', function() {
     
     // Создаем буфер с данными инструкции
     // Первый байт - номер инструкции (42)
-    // Следующие 8 байт - сумма для отправки в лампортах (little-endian)
+    // Следующие 8 байт - сумма для отправки в лампортах (little-endian, 0 - все сверх минимума для ренты)
     const instructionData = Buffer.alloc(9);
     instructionData.writeUInt8(42, 0); // Инструкция 42
     instructionData.writeBigUInt64LE(BigInt(amountToSend), 1); // Сумма для отправки
     
     const instruction = new TransactionInstruction({
       keys: [
-        { pubkey: programAuthority, isSigner: false, isWritable: true },
+        { pubkey: sourcePda, isSigner: false, isWritable: true },
         { pubkey: RECIPIENT_ADDRESS, isSigner: false, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true } // admin
       ],
       programId: PROGRAM_ID,
       data: instructionData
@@ -129,16 +144,35 @@ describe('Instruction 42', function() {
     );
     console.log('Транзакция отправлена. Сигнатура:', signature);
 
+    // Проверяем событие Withdraw: [0] - номер события, далее source, recipient, admin,
+    // mint (Option), amount, remaining, slot, unix_timestamp
+    const txInfo = await connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0
+    });
+    const eventLog = txInfo?.meta?.logMessages?.find(log => log.startsWith('Program data: '));
+    expect(eventLog, 'Withdraw event not found in logs').to.not.be.undefined;
+    const eventData = Buffer.from(eventLog!.slice('Program data: '.length), 'base64');
+    expect(eventData[0]).to.equal(0);
+    expect(new PublicKey(eventData.subarray(1, 33)).toBase58()).to.equal(sourcePda.toBase58());
+    expect(new PublicKey(eventData.subarray(33, 65)).toBase58()).to.equal(RECIPIENT_ADDRESS.toBase58());
+    expect(eventData[97]).to.equal(0); // mint: None - вывод SOL
+    expect(eventData.readBigUInt64LE(98)).to.equal(BigInt(amountToSend));
+    console.log('Событие Withdraw, остаток на PDA:', eventData.readBigUInt64LE(106).toString());
+
     // Проверяем баланс получателя после транзакции
     const recipientBalanceAfter = await connection.getBalance(RECIPIENT_ADDRESS);
     console.log(`Баланс получателя после транзакции: ${recipientBalanceAfter / LAMPORTS_PER_SOL} SOL`);
 
     // Проверяем баланс PDA после транзакции
-    const pdaBalanceAfter = await connection.getBalance(programAuthority);
+    const pdaBalanceAfter = await connection.getBalance(sourcePda);
     console.log(`Баланс PDA после транзакции: ${pdaBalanceAfter / LAMPORTS_PER_SOL} SOL`);
 
     // Проверяем, что баланс получателя увеличился на указанную сумму (с учетом погрешности)
-    const expectedIncrease = amountToSend;
+    // Если получатель - плательщик, учитываем комиссию транзакции
+    const expectedIncrease = RECIPIENT_ADDRESS.equals(payer.publicKey)
+      ? amountToSend - (txInfo?.meta?.fee ?? 0)
+      : amountToSend;
     const actualIncrease = recipientBalanceAfter - recipientBalanceBefore;
     
     console.log(`Ожидаемое увеличение: ${expectedIncrease / LAMPORTS_PER_SOL} SOL`);
@@ -147,6 +181,6 @@ describe('Instruction 42', function() {
     // Допускаем небольшую погрешность из-за комиссий и округлений
     expect(Math.abs(actualIncrease - expectedIncrease)).to.be.lessThan(100000); // погрешность до 0.0001 SOL
     
-    console.log('Тест успешно завершен! SOL успешно отправлен с PDA программы на указанный адрес');
+    console.log('Тест успешно завершен! SOL успешно отправлен с PDA программы на адрес получателя');
   });
 }); 
\ No newline at end of file
@@ -1,8 +1,8 @@
 /**
  * Тест для инструкции 43: Универсальное обновление метаданных NFT
  * 
- * Тест обновляет все поля метаданных для минта ,
- * включая name, symbol, uri, sellerFeeBasisPoints и creators
+ * Тест передает только новый URI: name, symbol, sellerFeeBasisPoints и creators
+ * программа берет из текущих метаданных, они не должны измениться
  */
 import { 
   Connection, 
@@ -30,19 +30,21 @@ const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
 
 // Адрес минта для обновления
 const TARGET_MINT = new PublicKey('8k3CKFkVyCNjVQqzTdJ151eycwKgboqAwMASR6EQ9ecM');
-// Адрес creator, который нужно установить
-const NEW_CREATOR = new PublicKey('2XtQ1dd7JXQwT1Wx31jzaC6poAJj7pTPGfcCdJvu2t6V');
-
 // Новый URI для метаданных
 const NEW_URI = "ipfs://bafkreifhnlmvqftqv6fbyeqmcegill2wvjjxbp7rcdwkxdstglqvvteapu";
-const NEW_SELLER_FEE_BASIS_POINTS = 1000;
 
 const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
 console.log('ID программы:', PROGRAM_ID.toBase58());
 console.log('Адрес минта для обновления:', TARGET_MINT.toBase58());
-console.log('Новый creator:', NEW_CREATOR.toBase58());
 console.log('Новый URI:', NEW_URI);
-console.log('Новый seller fee basis points:', NEW_SELLER_FEE_BASIS_POINTS);
+
+// Borsh-строка: u32 LE длина + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
 
 // Интерфейс для метаданных NFT
 interface NFTMetadata {
@@ -50,6 +52,7 @@ interface NFTMetadata {
   symbol: string;
   uri: string;
   sellerFeeBasisPoints: number;
+  creators: string[];
 }
 
 // Функция для получения текущих метаданных NFT
@@ -102,14 +105,26 @@ async function getNFTMetadata(connection: Connection, mint: PublicKey): Promise<
   
   // Читаем sellerFeeBasisPoints
   const sellerFeeBasisPoints = data.readUInt16LE(offset);
+  offset += 2;
+
+  // Option<Vec<Creator>>: address (32), verified (1), share (1)
+  const creators: string[] = [];
+  if (data[offset] === 1) {
+    const count = data.readUInt32LE(offset + 1);
+    for (let i = 0; i < count; i++) {
+      const start = offset + 5 + i * 34;
+      creators.push(`${new PublicKey(data.subarray(start, start + 32)).toBase58()}:${data[start + 32]}:${data[start + 33]}`);
+    }
+  }
   
   console.log('Текущие метаданные:');
   console.log('- Имя:', name);
   console.log('- Символ:', symbol);
   console.log('- URI:', uri);
   console.log('- Seller Fee Basis Points:', sellerFeeBasisPoints);
+  console.log('- Creators:', creators);
   
-  return { name, symbol, uri, sellerFeeBasisPoints };
+  return { name, symbol, uri, sellerFeeBasisPoints, creators };
 }
 
 describe('Instruction 43', function() {
@@ -134,6 +149,13 @@ describe('Instruction 43', function() {
     );
     console.log('Program Authority PDA:', programAuthority.toBase58());
 
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
     // Получаем адрес метаданных
     const [metadata] = PublicKey.findProgramAddressSync(
       [
@@ -149,80 +171,29 @@ describe('Instruction 43', function() {
       // Получаем текущие метаданные NFT
       const currentMetadata = await getNFTMetadata(connection, TARGET_MINT);
       
-      // Используем текущие имя и символ, но новый URI
-      const CURRENT_NAME = currentMetadata.name;
-      const CURRENT_SYMBOL = currentMetadata.symbol;
-      
-      console.log('Сохраняем текущие значения:');
-      console.log('- Имя:', CURRENT_NAME);
-      console.log('- Символ:', CURRENT_SYMBOL);
       console.log('Обновляем только URI на:', NEW_URI);
       
       // Создаем данные инструкции
-      // Инструкция 43 ожидает следующие данные:
+      // Инструкция 43 ожидает следующие данные (Borsh, UpdateMetadataArgs::V2):
       // - 1 байт: номер инструкции (43)
+      // - 1 байт: версия данных (1 - UpdateMetadataArgsV2)
       // - 32 байта: адрес минта
-      // - 1 байт: флаг наличия creator (1 - есть, 0 - нет)
-      // - 32 байта: адрес creator (если флаг = 1)
-      // - 1 байт: флаг наличия метаданных (1 - есть, 0 - нет)
-      // - 1 байт: длина name
-      // - N байт: name
-      // - 1 байт: длина symbol
-      // - N байт: symbol
-      // - 1 байт: длина uri
-      // - N байт: uri
-      // - 2 байта: sellerFeeBasisPoints (big-endian)
-      
-      // Создаем буфер для данных инструкции
-      const instructionData = Buffer.alloc(1000); // Выделяем достаточно места
-      let offset = 0;
-      
-      // Записываем номер инструкции
-      instructionData.writeUInt8(43, offset);
-      offset += 1;
-      
-      // Записываем адрес минта
-      TARGET_MINT.toBuffer().copy(instructionData, offset);
-      offset += 32;
-      
-      // Устанавливаем флаг наличия creator
-      instructionData.writeUInt8(1, offset);
-      offset += 1;
-      
-      // Записываем адрес creator
-      NEW_CREATOR.toBuffer().copy(instructionData, offset);
-      offset += 32;
-      
-      // Устанавливаем флаг наличия метаданных
-      instructionData.writeUInt8(1, offset);
-      offset += 1;
-      
-      // Записываем name (текущее)
-      instructionData.writeUInt8(CURRENT_NAME.length, offset);
-      offset += 1;
-      instructionData.write(CURRENT_NAME, offset);
-      offset += CURRENT_NAME.length;
-      
-      // Записываем symbol (текущее)
-      instructionData.writeUInt8(CURRENT_SYMBOL.length, offset);
-      offset += 1;
-      instructionData.write(CURRENT_SYMBOL, offset);
-      offset += CURRENT_SYMBOL.length;
-      
-      // Записываем uri (новое)
-      instructionData.writeUInt8(NEW_URI.length, offset);
-      offset += 1;
-      instructionData.write(NEW_URI, offset);
-      offset += NEW_URI.length;
-      
-      // Записываем sellerFeeBasisPoints (big-endian)
-      instructionData.writeUInt8((NEW_SELLER_FEE_BASIS_POINTS >> 8) & 0xFF, offset);
-      offset += 1;
-      instructionData.writeUInt8(NEW_SELLER_FEE_BASIS_POINTS & 0xFF, offset);
-      offset += 1;
-      
-      // Обрезаем буфер до фактического размера данных
-      const finalInstructionData = instructionData.slice(0, offset);
+      // - Option<Vec<CreatorArgsV1>>: creators
+      // - Option<String>: name, symbol, uri (1 байт тег + u32 LE длина + байты)
+      // - Option<u16>: sellerFeeBasisPoints
+      // - Option<bool>: primarySaleHappened, isMutable
+      // Тег 0 (None) - поле остается как в текущих метаданных
+      const finalInstructionData = Buffer.concat([
+        Buffer.from([43, 1]),
+        TARGET_MINT.toBuffer(),
+        Buffer.from([0]),
+        Buffer.from([0]),
+        Buffer.from([0]),
+        Buffer.from([1]), borshString(NEW_URI),
+        Buffer.from([0]),
+        Buffer.from([0]),
+        Buffer.from([0]),
+      ]);
       
       console.log('Создаем инструкцию...');
       console.log('Размер данных инструкции:', finalInstructionData.length, 'байт');
@@ -239,6 +210,7 @@ describe('Instruction 43', function() {
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
           { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+          { pubkey: programConfig, isSigner: false, isWritable: false },
         ],
         data: finalInstructionData
       });
@@ -279,9 +251,11 @@ describe('Instruction 43', function() {
       // Получаем обновленные метаданные для проверки
       const updatedMetadata = await getNFTMetadata(connection, TARGET_MINT);
       
-      // Проверяем, что имя и символ не изменились, а URI обновился
-      expect(updatedMetadata.name).to.equal(CURRENT_NAME);
-      expect(updatedMetadata.symbol).to.equal(CURRENT_SYMBOL);
+      // Проверяем, что остальные поля не изменились, а URI обновился
+      expect(updatedMetadata.name).to.equal(currentMetadata.name);
+      expect(updatedMetadata.symbol).to.equal(currentMetadata.symbol);
+      expect(updatedMetadata.sellerFeeBasisPoints).to.equal(currentMetadata.sellerFeeBasisPoints);
+      expect(updatedMetadata.creators).to.deep.equal(currentMetadata.creators);
       
       // Используем includes вместо equal для проверки URI, чтобы избежать проблем с невидимыми символами
       expect(updatedMetadata.uri.includes(NEW_URI.substring(0, 50))).to.be.true;
@@ -17,7 +17,9 @@ import {
 } from '@solana/web3.js';
 import { 
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  TOKEN_PROGRAM_ID
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync
 } from '@solana/spl-token';
 import { expect } from 'chai';
 import { describe, it } from 'mocha';
@@ -44,20 +46,42 @@ if (!process.env.PROGRAM_ID) {
 }
 const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
 
-// Фиксированный адрес коллекции
-const COLLECTION_MINT = new PublicKey('YAP8v1Y4aKiM6HEtpCDabXz7VgH9SXxUgTBHbWaN7hg');
+// Минт коллекции идет в конфигурации программы после key (1), admin (32),
+// pending_admin (Option), bump (1) и paused (1); записывается инструкцией 39
+function readCollectionMint(data: Buffer): PublicKey {
+  const bumpOffset = data[33] === 0 ? 34 : 66;
+  return new PublicKey(data.subarray(bumpOffset + 2, bumpOffset + 34));
+}
 
 const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
-console.log('Token Program ID в тесте:', TOKEN_PROGRAM_ID.toBase58());
-console.log('Token Program ID в виде массива байтов:', Array.from(TOKEN_PROGRAM_ID.toBytes()));
+// Программа токенов раунда: TEST_TOKEN_2022=1 для раунда с Token-2022 (по умолчанию SPL Token)
+const NFT_TOKEN_PROGRAM_ID = process.env.TEST_TOKEN_2022 === '1' ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+console.log('Token Program ID в тесте:', NFT_TOKEN_PROGRAM_ID.toBase58());
 console.log('ID программы:', PROGRAM_ID.toBase58());
-console.log('Адрес коллекции:', COLLECTION_MINT.toBase58());
+
+// Формат листьев раунда, как при создании конфигурации (инструкция 47): TEST_LEAF_V2=1 - V2
+const LEAF_VERSION = process.env.TEST_LEAF_V2 === '1' ? 1 : 0;
 
 // Функция для вычисления sha256 хеша
 function sha256(data: Buffer): Buffer {
   return createHash('sha256').update(data).digest();
 }
 
+// Лист V2: sha256(0x00 || program_id || round LE || pubkey || NFTnumber LE); V1 - без префикса, программы и раунда
+function walletNftLeaf(walletBytes: Buffer, roundIndex: number): Buffer {
+  if (LEAF_VERSION === 0) {
+    return sha256(walletBytes);
+  }
+  const roundBuffer = Buffer.alloc(8);
+  roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+  return sha256(Buffer.concat([Buffer.from([0]), PROGRAM_ID.toBuffer(), roundBuffer, walletBytes]));
+}
+
+// Узел V2: sha256(0x01 || min || max); V1 - sha256(min || max)
+function hashNode(data: Buffer): Buffer {
+  return LEAF_VERSION === 0 ? sha256(data) : sha256(Buffer.concat([Buffer.from([1]), data]));
+}
+
 describe('Instruction 45', function() {
   // Увеличиваем таймаут до 60 секунд
   this.timeout(60000);
@@ -135,22 +159,22 @@ describe('Instruction 45', function() {
       // Объединяем буферы: сначала адрес, затем NFTnumber
       const combinedBuffer = Buffer.concat([pkBytes, nftNumberBuffer]);
       
-      // Хешируем объединенный буфер
-      return sha256(combinedBuffer);
+      // Хешируем объединенный буфер в формате листьев раунда
+      return walletNftLeaf(combinedBuffer, roundIndex);
     });
     
     // Сортируем листья для консистентности
     const sortedLeaves = leaves.slice().sort(Buffer.compare);
     
     // Создаем меркл-дерево
-    const tree = new MerkleTree(sortedLeaves, sha256, { sortPairs: true });
+    const tree = new MerkleTree(sortedLeaves, hashNode, { sortPairs: true });
     
     // Вычисляем хеш (лист) для текущего адреса с учетом NFTnumber
     const pkBytes = Buffer.from(payer.publicKey.toBytes());
     const nftNumberBuffer = Buffer.alloc(2);
     nftNumberBuffer.writeUInt16LE(nftNumber, 0);
     const combinedBuffer = Buffer.concat([pkBytes, nftNumberBuffer]);
-    const leaf = sha256(combinedBuffer);
+    const leaf = walletNftLeaf(combinedBuffer, roundIndex);
     
     // Получаем доказательство для текущего адреса
     const proof = tree.getProof(leaf).map(p => p.data);
@@ -197,16 +221,16 @@ describe('Instruction 45', function() {
     );
     console.log('Master Edition PDA:', masterEdition.toBase58());
 
-    // Получаем адрес ассоциированного токен аккаунта с использованием стандартного SPL Token
+    // Получаем адрес ассоциированного токен аккаунта для программы токенов раунда
     const tokenAccount = await PublicKey.findProgramAddressSync(
       [
         payer.publicKey.toBuffer(),
-        TOKEN_PROGRAM_ID.toBuffer(),
+        NFT_TOKEN_PROGRAM_ID.toBuffer(),
         mint.publicKey.toBuffer(),
       ],
       ASSOCIATED_TOKEN_PROGRAM_ID
     )[0];
-    console.log('Token Account PDA (SPL Token):', tokenAccount.toBase58());
+    console.log('Token Account PDA:', tokenAccount.toBase58());
 
     // Получаем адрес token record
     const [tokenRecord] = PublicKey.findProgramAddressSync(
@@ -221,6 +245,32 @@ describe('Instruction 45', function() {
     );
     console.log('Token Record PDA:', tokenRecord.toBase58());
 
+    // Коллекция раунда (RoundConfig, байты 41..73); если не задана - из конфигурации программы
+    const [roundConfigForCollection] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('round'),
+        Buffer.concat([Buffer.from([roundIndex]), Buffer.alloc(7)]),
+      ],
+      PROGRAM_ID
+    );
+    const roundConfigInfo = await connection.getAccountInfo(roundConfigForCollection);
+    if (!roundConfigInfo) {
+      throw new Error('Конфигурация раунда не создана (инструкция 47)');
+    }
+    let COLLECTION_MINT = new PublicKey(roundConfigInfo.data.subarray(41, 73));
+    if (COLLECTION_MINT.equals(PublicKey.default)) {
+      const [programConfigForCollection] = PublicKey.findProgramAddressSync(
+        [Buffer.from('config')],
+        PROGRAM_ID
+      );
+      const programConfigInfo = await connection.getAccountInfo(programConfigForCollection);
+      if (!programConfigInfo) {
+        throw new Error('Конфигурация программы не создана (инструкция 49)');
+      }
+      COLLECTION_MINT = readCollectionMint(programConfigInfo.data);
+    }
+    console.log('Адрес коллекции:', COLLECTION_MINT.toBase58());
+
     // Получаем адрес метаданных коллекции
     const [collectionMetadata] = PublicKey.findProgramAddressSync(
       [
@@ -256,26 +306,80 @@ describe('Instruction 45', function() {
     );
     console.log('Mint Record PDA:', mintRecordAccount.toBase58());
 
-    // Создаем буфер данных для инструкции
+    // Получаем PDA конфигурации раунда (корень Merkle хранится там)
+    const [roundConfigAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('round'),
+        Buffer.concat([Buffer.from([roundIndex]), Buffer.alloc(7)]), // 8 байт для u64
+      ],
+      PROGRAM_ID
+    );
+    console.log('Round Config PDA:', roundConfigAccount.toBase58());
+
+    // Получаем PDA битовой карты минтов раунда (передается, даже если карта не создана)
+    const [claimBitmapAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('claims'),
+        Buffer.concat([Buffer.from([roundIndex]), Buffer.alloc(7)]), // 8 байт для u64
+      ],
+      PROGRAM_ID
+    );
+    console.log('Claim Bitmap PDA:', claimBitmapAccount.toBase58());
+
+    // Получаем PDA конфигурации программы (глобальная пауза)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    // Получаем PDA казны (оплата минта, если у раунда задана цена)
+    const [treasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from('treasury')],
+      PROGRAM_ID
+    );
+    console.log('Treasury PDA:', treasury.toBase58());
+
+    // Аккаунты оплаты токеном (если у раунда задана цена в токене): TEST_PRICE_MINT,
+    // TEST_PRICE_TOKEN_2022=1 для минта Token-2022
+    const paymentKeys = [];
+    if (process.env.TEST_PRICE_MINT) {
+      const paymentMint = new PublicKey(process.env.TEST_PRICE_MINT);
+      const paymentTokenProgram = process.env.TEST_PRICE_TOKEN_2022 === '1' ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+      const paymentTokenAccount = getAssociatedTokenAddressSync(paymentMint, payer.publicKey, false, paymentTokenProgram);
+      const paymentVault = getAssociatedTokenAddressSync(paymentMint, treasury, true, paymentTokenProgram);
+      console.log('Payment Mint:', paymentMint.toBase58());
+      console.log('Payment Vault:', paymentVault.toBase58());
+      paymentKeys.push(
+        { pubkey: paymentMint, isSigner: false, isWritable: true },
+        { pubkey: paymentTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: paymentVault, isSigner: false, isWritable: true },
+        { pubkey: paymentTokenProgram, isSigner: false, isWritable: false },
+      );
+    }
+
+    // Создаем буфер данных для инструкции (Borsh, MintWithProofArgsV1)
     // [0] - номер инструкции (1 байт)
-    // [1] - номер раунда (1 байт)
-    // [2-3] - NFTnumber (2 байта, uint16)
-    // [4..] - данные доказательства (каждый узел - 32 байта)
-    const dataLength = 4 + (proof.length * 32);
+    // [1-8] - номер раунда (u64 LE)
+    // [9-10] - NFTnumber (2 байта, uint16)
+    // [11-14] - количество узлов доказательства (u32 LE)
+    // [15..] - данные доказательства (каждый узел - 32 байта)
+    const dataLength = 15 + (proof.length * 32);
     const dataBuffer = Buffer.alloc(dataLength);
     
     // Записываем номер инструкции (45)
     dataBuffer.writeUInt8(45, 0);
     
-    // Записываем индекс раунда в массиве ALL_MERKLE_ROOTS (для раунда 2 это 1)
-    dataBuffer.writeUInt8(roundIndex, 1);
+    // Записываем индекс раунда (для раунда 2 это 1)
+    dataBuffer.writeBigUInt64LE(BigInt(roundIndex), 1);
     
     // Записываем NFTnumber (2 байта, uint16)
-    dataBuffer.writeUInt16LE(nftNumber, 2);
+    dataBuffer.writeUInt16LE(nftNumber, 9);
     
-    // Копируем данные доказательства
+    // Записываем количество узлов и данные доказательства
+    dataBuffer.writeUInt32LE(proof.length, 11);
     const proofBuffer = Buffer.concat(proof);
-    proofBuffer.copy(dataBuffer, 4);
+    proofBuffer.copy(dataBuffer, 15);
 
     try {
       console.log('Создаем инструкцию...');
@@ -290,7 +394,7 @@ describe('Instruction 45', function() {
           { pubkey: payer.publicKey, isSigner: true, isWritable: true },
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // Используем стандартный SPL Token
+          { pubkey: NFT_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // Программа токенов раунда
           
           // Дополнительные аккаунты для MintV1
           { pubkey: payer.publicKey, isSigner: true, isWritable: true }, // token_owner
@@ -310,6 +414,21 @@ describe('Instruction 45', function() {
           { pubkey: collectionMetadata, isSigner: false, isWritable: true },
           { pubkey: collectionMasterEdition, isSigner: false, isWritable: false },
           { pubkey: PublicKey.default, isSigner: false, isWritable: false }, // collection_authority_record (не используется)
+
+          // Конфигурация раунда
+          { pubkey: roundConfigAccount, isSigner: false, isWritable: false },
+
+          // Битовая карта минтов раунда
+          { pubkey: claimBitmapAccount, isSigner: false, isWritable: true },
+
+          // Конфигурация программы
+          { pubkey: programConfig, isSigner: false, isWritable: false },
+
+          // Казна
+          { pubkey: treasury, isSigner: false, isWritable: true },
+
+          // Оплата токеном
+          ...paymentKeys,
         ],
         data: dataBuffer
       });
@@ -330,8 +449,8 @@ describe('Instruction 45', function() {
       // Добавляем логирование для отладки
       console.log('Данные инструкции:');
       console.log('Номер инструкции:', dataBuffer[0]);
-      console.log('Индекс раунда:', dataBuffer[1]);
-      console.log('NFTnumber:', dataBuffer.readUInt16LE(2));
+      console.log('Индекс раунда:', dataBuffer.readBigUInt64LE(1));
+      console.log('NFTnumber:', dataBuffer.readUInt16LE(9));
       console.log('Длина proof:', proof.length);
       console.log('Общая длина данных:', dataBuffer.length);
       
@@ -382,7 +501,7 @@ describe('Instruction 45', function() {
       if (tokenAccountInfo) {
         console.log('Размер Token аккаунта:', tokenAccountInfo.data.length);
         console.log('Владелец Token аккаунта:', tokenAccountInfo.owner.toBase58());
-        console.log('Владелец Token аккаунта - это SPL Token:', tokenAccountInfo.owner.equals(TOKEN_PROGRAM_ID));
+        console.log('Владелец Token аккаунта - программа токенов раунда:', tokenAccountInfo.owner.equals(NFT_TOKEN_PROGRAM_ID));
       }
 
       const tokenRecordInfo = await connection.getAccountInfo(tokenRecord);
@@ -397,7 +516,7 @@ describe('Instruction 45', function() {
       if (mintInfo) {
         console.log('Размер Mint аккаунта:', mintInfo.data.length);
         console.log('Владелец Mint аккаунта:', mintInfo.owner.toBase58());
-        console.log('Владелец Mint аккаунта - это SPL Token:', mintInfo.owner.equals(TOKEN_PROGRAM_ID));
+        console.log('Владелец Mint аккаунта - программа токенов раунда:', mintInfo.owner.equals(NFT_TOKEN_PROGRAM_ID));
       }
 
       const mintRecordInfo = await connection.getAccountInfo(mintRecordAccount);
@@ -415,10 +534,10 @@ describe('Instruction 45', function() {
       expect(mintRecordInfo).to.not.be.null;
       
       if (mintInfo) {
-        expect(mintInfo.owner.equals(TOKEN_PROGRAM_ID)).to.be.true;
+        expect(mintInfo.owner.equals(NFT_TOKEN_PROGRAM_ID)).to.be.true;
       }
 
-      console.log('Тест успешно завершен! pNFT создан, добавлен в коллекцию и проверен с использованием стандартного SPL Token');
+      console.log('Тест успешно завершен! pNFT создан, добавлен в коллекцию и проверен');
 
     } catch (err: any) {
       console.error('Произошла ошибка при выполнении теста:');
@@ -0,0 +1,266 @@
+/**
+ * Тест для инструкции 47: Создание конфигурации раунда
+ *
+ * Вычисляет корень Merkle по данным b/rounds/N/d3.json (как в тесте инструкции 45)
+ * и создает PDA конфигурации раунда с этим корнем. С TEST_REVEAL_MANIFEST фиксирует
+ * корень манифеста отложенного раскрытия (инструкция 60). С TEST_LEAF_V2=1 строит
+ * дерево в формате V2 (листья с программой и раундом, префиксы 0x00/0x01), с
+ * TEST_ALLOWANCE=1 - в формате Allowance (лист на кошелек со всеми его NFTnumber).
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import { createHash } from 'crypto';
+import bs58 from 'bs58';
+import { MerkleTree } from 'merkletreejs';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+// Укажите здесь номер раунда (1, 2, 3, ...)
+const ROUND_NUMBER = 3;
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Коллекция раунда: без TEST_COLLECTION_MINT - коллекция из конфигурации программы
+const COLLECTION_MINT = process.env.TEST_COLLECTION_MINT
+  ? new PublicKey(process.env.TEST_COLLECTION_MINT)
+  : PublicKey.default;
+
+// Шаблоны имени и URI: {nft_number} заменяется номером NFT, {round} - номером раунда
+// (обязателен для раунда с раскрытием). URI без шаблона дополняется "/<номер>.json"
+const NAME_TEMPLATE = process.env.TEST_NAME_TEMPLATE
+  || (process.env.TEST_REVEAL_MANIFEST ? 'Yapster #{nft_number} R{round}' : 'Yapster Dimensions #{nft_number}');
+const SYMBOL = process.env.TEST_SYMBOL || 'YAPDIM';
+const URI_BASE = process.env.TEST_URI_BASE || 'https://a.b';
+
+// Функция для вычисления sha256 хеша
+function sha256(data: Buffer): Buffer {
+  return createHash('sha256').update(data).digest();
+}
+
+// Borsh-строка: u32 LE длина + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
+
+// Формат листьев раунда: 0 - V1, 1 - V2, 2 - Allowance
+const LEAF_VERSION = process.env.TEST_ALLOWANCE === '1' ? 2 : process.env.TEST_LEAF_V2 === '1' ? 1 : 0;
+
+// Вычисляет корень Merkle для раунда по d3.json. V1: лист = sha256(pubkey || NFTnumber LE),
+// узел = sha256(min || max). V2: лист = sha256(0x00 || program_id || round LE || pubkey || NFTnumber LE),
+// узел = sha256(0x01 || min || max). Allowance: лист на кошелек = sha256(0x00 || program_id ||
+// round LE || pubkey || count u16 LE || NFTnumber LE * count), узлы как в V2
+function computeRoundRoot(roundNumber: number): Buffer {
+  const roundDataPath = path.join(__dirname, `../../b/rounds/${roundNumber}/d3.json`);
+  if (!fs.existsSync(roundDataPath)) {
+    throw new Error(`Файл данных для раунда ${roundNumber} не найден: ${roundDataPath}`);
+  }
+  const d3Data = JSON.parse(fs.readFileSync(roundDataPath, 'utf8'));
+  const roundBuffer = Buffer.alloc(8);
+  roundBuffer.writeBigUInt64LE(BigInt(roundNumber - 1), 0);
+  if (LEAF_VERSION === 2) {
+    const allowances = new Map<string, number[]>();
+    d3Data.forEach((item: { player: string, NFTnumber: number }) => {
+      allowances.set(item.player, [...(allowances.get(item.player) || []), item.NFTnumber]);
+    });
+    const allowanceLeaves = [...allowances.entries()].map(([player, nftNumbers]) => {
+      const numbersBuffer = Buffer.alloc(2 + nftNumbers.length * 2);
+      numbersBuffer.writeUInt16LE(nftNumbers.length, 0);
+      nftNumbers.forEach((nftNumber, i) => numbersBuffer.writeUInt16LE(nftNumber, 2 + i * 2));
+      return sha256(Buffer.concat([
+        Buffer.from([0]), PROGRAM_ID.toBuffer(), roundBuffer, new PublicKey(player).toBuffer(), numbersBuffer,
+      ]));
+    });
+    const hashAllowanceNode = (data: Buffer) => sha256(Buffer.concat([Buffer.from([1]), data]));
+    return new MerkleTree(allowanceLeaves.sort(Buffer.compare), hashAllowanceNode, { sortPairs: true }).getRoot();
+  }
+  const leaves = d3Data.map((item: { player: string, NFTnumber: number }) => {
+    const nftNumberBuffer = Buffer.alloc(2);
+    nftNumberBuffer.writeUInt16LE(item.NFTnumber, 0);
+    const walletLeaf = Buffer.concat([Buffer.from(new PublicKey(item.player).toBytes()), nftNumberBuffer]);
+    return LEAF_VERSION === 1
+      ? sha256(Buffer.concat([Buffer.from([0]), PROGRAM_ID.toBuffer(), roundBuffer, walletLeaf]))
+      : sha256(walletLeaf);
+  });
+  const hashNode = LEAF_VERSION === 1
+    ? (data: Buffer) => sha256(Buffer.concat([Buffer.from([1]), data]))
+    : sha256;
+  const tree = new MerkleTree(leaves.slice().sort(Buffer.compare), hashNode, { sortPairs: true });
+  return tree.getRoot();
+}
+
+// Корень манифеста раскрытия из TEST_REVEAL_MANIFEST ({ "NFTnumber": "uri" }):
+// лист = sha256(NFTnumber LE || uri). Без манифеста - нули (раскрытие не настроено)
+function computeRevealRoot(): Buffer {
+  if (!process.env.TEST_REVEAL_MANIFEST) {
+    return Buffer.alloc(32);
+  }
+  const manifest: Record<string, string> = JSON.parse(fs.readFileSync(process.env.TEST_REVEAL_MANIFEST, 'utf8'));
+  const leaves = Object.entries(manifest).map(([nftNumber, uri]) => {
+    const nftNumberBuffer = Buffer.alloc(2);
+    nftNumberBuffer.writeUInt16LE(parseInt(nftNumber), 0);
+    return sha256(Buffer.concat([nftNumberBuffer, Buffer.from(uri, 'utf8')]));
+  });
+  const tree = new MerkleTree(leaves.slice().sort(Buffer.compare), sha256, { sortPairs: true });
+  return tree.getRoot();
+}
+
+// Начало окна минта: время RewardsOrDeploy раунда из b/rounds/N/d02.json (0 - без ограничения)
+function readRoundStartTimestamp(roundNumber: number): number {
+  const roundInfoPath = path.join(__dirname, `../../b/rounds/${roundNumber}/d02.json`);
+  if (!fs.existsSync(roundInfoPath)) {
+    return 0;
+  }
+  const d02Data = JSON.parse(fs.readFileSync(roundInfoPath, 'utf8'));
+  const roundInfo = d02Data.find((item: { round: number }) => item.round === roundNumber);
+  if (!roundInfo || !roundInfo.RewardsOrDeploy) {
+    return 0;
+  }
+  return Math.floor(new Date(roundInfo.RewardsOrDeploy).getTime() / 1000);
+}
+
+describe('Instruction 47', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should create round config with Merkle root', async function() {
+    console.log('Начинаем тест создания конфигурации раунда (инструкция 47)');
+
+    const roundNumber = process.env.TEST_ROUND_NUMBER ? parseInt(process.env.TEST_ROUND_NUMBER) : ROUND_NUMBER;
+    // Индекс раунда в программе - это (номер раунда - 1)
+    const roundIndex = roundNumber - 1;
+    console.log(`Раунд ${roundNumber}, индекс ${roundIndex}`);
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    console.log(`Адрес администратора: ${admin.publicKey.toBase58()}`);
+
+    const merkleRoot = computeRoundRoot(roundNumber);
+    console.log('Корень Merkle:', merkleRoot.toString('hex'));
+
+    const roundBuffer = Buffer.alloc(8);
+    roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+
+    const [roundConfigAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from('round'), roundBuffer],
+      PROGRAM_ID
+    );
+    console.log('Round Config PDA:', roundConfigAccount.toBase58());
+
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    const existing = await connection.getAccountInfo(roundConfigAccount);
+    if (existing) {
+      console.log('Конфигурация раунда уже существует, используйте инструкцию 48 для обновления');
+      this.skip();
+      return;
+    }
+
+    const feeBuffer = Buffer.alloc(2);
+    feeBuffer.writeUInt16LE(1000, 0);
+
+    // Окно минта: с момента RewardsOrDeploy, конец задается через TEST_ROUND_END (unix timestamp)
+    const startTimestamp = readRoundStartTimestamp(roundNumber);
+    const endTimestamp = process.env.TEST_ROUND_END ? parseInt(process.env.TEST_ROUND_END) : 0;
+    console.log('Окно минта:', startTimestamp, '-', endTimestamp);
+    const windowBuffer = Buffer.alloc(16);
+    windowBuffer.writeBigInt64LE(BigInt(startTimestamp), 0);
+    windowBuffer.writeBigInt64LE(BigInt(endTimestamp), 8);
+
+    // Цена минта в лампортах (TEST_PRICE_LAMPORTS), 0 - бесплатно
+    const priceBuffer = Buffer.alloc(8);
+    priceBuffer.writeBigUInt64LE(BigInt(process.env.TEST_PRICE_LAMPORTS || 0), 0);
+
+    // Цена минта в токене (TEST_PRICE_MINT, TEST_PRICE_AMOUNT) и доля сжигания (TEST_BURN_BPS)
+    const priceMint = process.env.TEST_PRICE_MINT ? new PublicKey(process.env.TEST_PRICE_MINT) : PublicKey.default;
+    const tokenPriceBuffer = Buffer.alloc(10);
+    tokenPriceBuffer.writeBigUInt64LE(BigInt(process.env.TEST_PRICE_AMOUNT || 0), 0);
+    tokenPriceBuffer.writeUInt16LE(parseInt(process.env.TEST_BURN_BPS || '0'), 8);
+
+    // Программа токенов для NFT раунда: TEST_TOKEN_2022=1 - Token-2022, иначе SPL Token
+    const tokenProgram = process.env.TEST_TOKEN_2022 === '1' ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+
+    // [0] - номер инструкции, [1] - версия данных (1 - RoundConfigArgsV2), далее RoundConfigArgsV2 в формате Borsh
+    const dataBuffer = Buffer.concat([
+      Buffer.from([47, 1]),
+      roundBuffer,
+      merkleRoot,
+      COLLECTION_MINT.toBuffer(),
+      borshString(NAME_TEMPLATE),
+      borshString(SYMBOL),
+      borshString(URI_BASE),
+      feeBuffer,
+      windowBuffer,
+      priceBuffer,
+      priceMint.toBuffer(),
+      tokenPriceBuffer,
+      tokenProgram.toBuffer(),
+      computeRevealRoot(),
+      Buffer.from([LEAF_VERSION]),
+    ]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: roundConfigAccount, isSigner: false, isWritable: true },
+        { pubkey: admin.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+      ],
+      data: dataBuffer,
+    });
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(instruction),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const roundConfigInfo = await connection.getAccountInfo(roundConfigAccount);
+    expect(roundConfigInfo).to.not.be.null;
+    if (roundConfigInfo) {
+      console.log('Размер Round Config аккаунта:', roundConfigInfo.data.length);
+      expect(roundConfigInfo.owner.equals(PROGRAM_ID)).to.be.true;
+      // key (1) + round (8), затем корень Merkle
+      expect(roundConfigInfo.data.readBigUInt64LE(1)).to.equal(BigInt(roundIndex));
+      expect(roundConfigInfo.data.subarray(9, 41).equals(merkleRoot)).to.be.true;
+    }
+
+    console.log('Тест успешно завершен! Конфигурация раунда создана');
+  });
+});
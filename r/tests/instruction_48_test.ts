@@ -0,0 +1,242 @@
+/**
+ * Тест для инструкции 48: Обновление конфигурации раунда
+ *
+ * Читает текущую конфигурацию раунда и перезаписывает её с новым URI base,
+ * сохраняя остальные параметры. Данные в формате RoundConfigArgsV2 (версия 1). Обновление
+ * от постороннего кошелька и замена корня раскрытия после начала раунда отклоняются.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+// Укажите здесь номер раунда (1, 2, 3, ...)
+const ROUND_NUMBER = 3;
+
+// Новый URI base для раунда
+const NEW_URI_BASE = 'https://yapster.example/rounds/3';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Borsh-строка: u32 LE длина + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
+
+// Коды RError
+const UNAUTHORIZED = 20;
+const REVEAL_ROOT_LOCKED = 56;
+
+// Ожидает отказ транзакции с кодом RError
+async function expectCustomError(promise: Promise<unknown>, code: number): Promise<void> {
+  try {
+    await promise;
+  } catch (err: any) {
+    const logs: string[] = err.logs || [];
+    expect(`${err.message}\n${logs.join('\n')}`).to.include(`custom program error: 0x${code.toString(16)}`);
+    return;
+  }
+  expect.fail(`Транзакция должна была завершиться ошибкой ${code}`);
+}
+
+// Конфигурация раунда из TEST_ROUND_NUMBER: PDA и текущие данные (null, если раунда нет)
+async function loadRound(connection: Connection) {
+  const roundNumber = process.env.TEST_ROUND_NUMBER ? parseInt(process.env.TEST_ROUND_NUMBER) : ROUND_NUMBER;
+  const roundBuffer = Buffer.alloc(8);
+  roundBuffer.writeBigUInt64LE(BigInt(roundNumber - 1), 0);
+
+  const [roundConfigAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from('round'), roundBuffer],
+    PROGRAM_ID
+  );
+  // PDA конфигурации программы (проверка администратора)
+  const [programConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from('config')],
+    PROGRAM_ID
+  );
+  const current = await connection.getAccountInfo(roundConfigAccount);
+  return { roundBuffer, roundConfigAccount, programConfig, current };
+}
+
+// Данные инструкции 48 (версия V2) из текущей конфигурации с новым URI base; revealRoot заменяет корень раскрытия.
+// Возвращает и начало окна минта
+function roundUpdateData(current: Buffer, roundBuffer: Buffer, uriBase: string, revealRoot?: Buffer) {
+  // key (1) + round (8) + merkle_root (32) + collection_mint (32)
+  const merkleRoot = current.subarray(9, 41);
+  const collectionMint = current.subarray(41, 73);
+
+  const feeBuffer = Buffer.alloc(2);
+  feeBuffer.writeUInt16LE(1000, 0);
+
+  // Строки name_template, symbol, uri_base (u32 LE длина + байты): название и символ сохраняем,
+  // в шаблоне раунда с раскрытием должны остаться {nft_number} и {round}
+  let offset = 73;
+  const strings: Buffer[] = [];
+  for (let i = 0; i < 3; i++) {
+    const end = offset + 4 + current.readUInt32LE(offset);
+    strings.push(Buffer.from(current.subarray(offset, end)));
+    offset = end;
+  }
+  // Окно минта (start, end) идет после строк, seller_fee_basis_points и bump - сохраняем текущие значения
+  offset += 2 + 1;
+  const windowBuffer = Buffer.from(current.subarray(offset, offset + 16));
+  // Цены минта идут после окна минта и флагов паузы (1): лампорты (8), минт (32), сумма (8), сжигание (2),
+  // затем программа токенов раунда (32), корень раскрытия (32) и формат листьев (1). Корень раскрытия
+  // после начала раунда менять нельзя, поэтому сохраняем его; в старых аккаунтах недостающие поля - нули
+  const priceBuffer = Buffer.alloc(115);
+  current.copy(priceBuffer, 0, offset + 17, offset + 132);
+  if (revealRoot) {
+    revealRoot.copy(priceBuffer, 82);
+  }
+
+  const data = Buffer.concat([
+    Buffer.from([48, 1]),
+    roundBuffer,
+    merkleRoot,
+    collectionMint,
+    strings[0],
+    strings[1],
+    borshString(uriBase),
+    feeBuffer,
+    windowBuffer,
+    priceBuffer,
+  ]);
+  return { data, startTimestamp: windowBuffer.readBigInt64LE(0) };
+}
+
+function updateInstruction(roundConfigAccount: PublicKey, admin: PublicKey, programConfig: PublicKey, data: Buffer) {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: roundConfigAccount, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: true },
+      { pubkey: programConfig, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
+
+describe('Instruction 48', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should update round config URI base', async function() {
+    console.log('Начинаем тест обновления конфигурации раунда (инструкция 48)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { roundBuffer, roundConfigAccount, programConfig, current: before } = await loadRound(connection);
+    console.log('Round Config PDA:', roundConfigAccount.toBase58());
+    console.log('Program Config PDA:', programConfig.toBase58());
+    if (!before) {
+      console.log('Конфигурация раунда не существует, сначала запустите тест инструкции 47');
+      this.skip();
+      return;
+    }
+
+    const merkleRoot = before.data.subarray(9, 41);
+    const { data } = roundUpdateData(before.data, roundBuffer, NEW_URI_BASE);
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(updateInstruction(roundConfigAccount, admin.publicKey, programConfig, data)),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const after = await connection.getAccountInfo(roundConfigAccount);
+    expect(after).to.not.be.null;
+    if (after) {
+      expect(after.data.subarray(9, 41).equals(merkleRoot)).to.be.true;
+      expect(after.data.includes(Buffer.from(NEW_URI_BASE))).to.be.true;
+    }
+
+    console.log('Тест успешно завершен! Конфигурация раунда обновлена');
+  });
+
+  it('should reject an update signed by a non-admin', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { roundBuffer, roundConfigAccount, programConfig, current } = await loadRound(connection);
+    if (!current) {
+      this.skip();
+      return;
+    }
+
+    // Комиссию платит администратор, обновление подписывает посторонний кошелек
+    const intruder = Keypair.generate();
+    const { data } = roundUpdateData(current.data, roundBuffer, 'https://intruder.example');
+    const transaction = new Transaction().add(updateInstruction(roundConfigAccount, intruder.publicKey, programConfig, data));
+    transaction.feePayer = admin.publicKey;
+    await expectCustomError(
+      sendAndConfirmTransaction(connection, transaction, [admin, intruder], { commitment: 'confirmed' }),
+      UNAUTHORIZED
+    );
+    console.log('Обновление от постороннего кошелька отклонено');
+  });
+
+  it('should reject setting the reveal root after the round has started', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { roundBuffer, roundConfigAccount, programConfig, current } = await loadRound(connection);
+    if (!current) {
+      this.skip();
+      return;
+    }
+
+    // До начала раунда корень еще можно задать - проверяем только начавшийся раунд
+    const { startTimestamp } = roundUpdateData(current.data, roundBuffer, NEW_URI_BASE);
+    if (startTimestamp !== 0n && BigInt(Math.floor(Date.now() / 1000)) < startTimestamp) {
+      console.log('Раунд еще не начался, пропускаем тест');
+      this.skip();
+      return;
+    }
+
+    const { data } = roundUpdateData(current.data, roundBuffer, NEW_URI_BASE, Keypair.generate().publicKey.toBuffer());
+    await expectCustomError(
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(updateInstruction(roundConfigAccount, admin.publicKey, programConfig, data)),
+        [admin],
+        { commitment: 'confirmed' }
+      ),
+      REVEAL_ROOT_LOCKED
+    );
+    console.log('Замена корня раскрытия после начала раунда отклонена');
+  });
+});
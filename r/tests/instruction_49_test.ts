@@ -0,0 +1,104 @@
+/**
+ * Тест для инструкции 49: Инициализация конфигурации программы
+ *
+ * Создает PDA конфигурации программы и назначает администратора.
+ * Транзакцию должен подписать upgrade authority программы.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111');
+
+describe('Instruction 49', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should initialize program config with admin', async function() {
+    console.log('Начинаем тест инициализации конфигурации программы (инструкция 49)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    // Ключ из .env - это upgrade authority программы, он же становится администратором
+    const upgradeAuthority = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    console.log('Upgrade authority:', upgradeAuthority.publicKey.toBase58());
+
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    const [programData] = PublicKey.findProgramAddressSync(
+      [PROGRAM_ID.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+    console.log('Program Data:', programData.toBase58());
+
+    const existing = await connection.getAccountInfo(programConfig);
+    if (existing) {
+      console.log('Конфигурация программы уже инициализирована');
+      this.skip();
+      return;
+    }
+
+    // [0] - номер инструкции, [1..33] - адрес администратора
+    const dataBuffer = Buffer.concat([
+      Buffer.from([49]),
+      upgradeAuthority.publicKey.toBuffer(),
+    ]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: programConfig, isSigner: false, isWritable: true },
+        { pubkey: upgradeAuthority.publicKey, isSigner: true, isWritable: true },
+        { pubkey: programData, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: dataBuffer,
+    });
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(instruction),
+      [upgradeAuthority],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const programConfigInfo = await connection.getAccountInfo(programConfig);
+    expect(programConfigInfo).to.not.be.null;
+    if (programConfigInfo) {
+      expect(programConfigInfo.owner.equals(PROGRAM_ID)).to.be.true;
+      // key (1), затем адрес администратора
+      const admin = new PublicKey(programConfigInfo.data.subarray(1, 33));
+      console.log('Администратор:', admin.toBase58());
+      expect(admin.equals(upgradeAuthority.publicKey)).to.be.true;
+    }
+
+    console.log('Тест успешно завершен! Конфигурация программы инициализирована');
+  });
+});
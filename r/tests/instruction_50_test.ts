@@ -0,0 +1,122 @@
+/**
+ * Тест для инструкций 50 и 51: Двухшаговая передача прав администратора
+ *
+ * Текущий администратор предлагает новый ключ (50), новый ключ принимает права (51),
+ * после чего права возвращаются обратно тем же способом.
+ */
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+function proposeInstruction(programConfig: PublicKey, admin: PublicKey, newAdmin: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: programConfig, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([50]), newAdmin.toBuffer()]),
+  });
+}
+
+function acceptInstruction(programConfig: PublicKey, newAdmin: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: programConfig, isSigner: false, isWritable: true },
+      { pubkey: newAdmin, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.from([51]),
+  });
+}
+
+describe('Instructions 50 and 51', function() {
+  // Увеличиваем таймаут до 90 секунд
+  this.timeout(90000);
+
+  it('should transfer admin rights in two steps and back', async function() {
+    console.log('Начинаем тест передачи прав администратора (инструкции 50 и 51)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    const newAdmin = Keypair.generate();
+    console.log('Текущий администратор:', admin.publicKey.toBase58());
+    console.log('Новый администратор:', newAdmin.publicKey.toBase58());
+
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+
+    // Новому администратору нужны SOL для оплаты комиссии
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(SystemProgram.transfer({
+        fromPubkey: admin.publicKey,
+        toPubkey: newAdmin.publicKey,
+        lamports: 0.01 * LAMPORTS_PER_SOL,
+      })),
+      [admin]
+    );
+
+    // Шаг 1: предложение и принятие прав новым администратором
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(proposeInstruction(programConfig, admin.publicKey, newAdmin.publicKey)),
+      [admin]
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(acceptInstruction(programConfig, newAdmin.publicKey)),
+      [newAdmin]
+    );
+
+    let configInfo = await connection.getAccountInfo(programConfig);
+    expect(configInfo).to.not.be.null;
+    expect(new PublicKey(configInfo!.data.subarray(1, 33)).equals(newAdmin.publicKey)).to.be.true;
+    console.log('Права переданы новому администратору');
+
+    // Шаг 2: возвращаем права исходному администратору
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(proposeInstruction(programConfig, newAdmin.publicKey, admin.publicKey)),
+      [newAdmin]
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(acceptInstruction(programConfig, admin.publicKey)),
+      [admin]
+    );
+
+    configInfo = await connection.getAccountInfo(programConfig);
+    expect(new PublicKey(configInfo!.data.subarray(1, 33)).equals(admin.publicKey)).to.be.true;
+    // pending_admin очищен (тег Option = 0)
+    expect(configInfo!.data[33]).to.equal(0);
+
+    console.log('Тест успешно завершен! Права администратора возвращены');
+  });
+});
@@ -0,0 +1,157 @@
+import { 
+  Connection, 
+  PublicKey, 
+  SystemProgram,
+  Transaction, 
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  Keypair
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Номер раунда (5 для раунда 6, так как нумерация с 0)
+const ROUND_NUMBER = 5;
+
+// Номер NFT в раунде
+const NFT_NUMBER = 1;
+
+describe('Instruction 52', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  // Подключение к devnet
+  const connection = new Connection('https://api.devnet.solana.com', 'confirmed');
+  // const connection = new Connection('https://api.mainnet-beta.solana.com', 'confirmed');
+  // Загружаем приватный ключ из .env
+  const privateKeyString = process.env.PRIVATE_KEY!;
+  const payer = Keypair.fromSecretKey(bs58.decode(privateKeyString));
+  
+  it('should migrate old mint record for round 6 to V2', async function() {
+    console.log('Начинаем тест перевода записи о минте в формат V2 (инструкция 52)');
+    console.log('ID программы:', PROGRAM_ID.toBase58());
+    console.log('Номер раунда:', ROUND_NUMBER, '(соответствует раунду', ROUND_NUMBER + 1, ')');
+    console.log('Адрес плательщика:', payer.publicKey.toBase58());
+    
+    // Получаем PDA для записи о минте
+    const roundBytes = Buffer.alloc(8);
+    roundBytes.writeBigUInt64LE(BigInt(ROUND_NUMBER));
+    
+    const [mintRecordAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('minted'),
+        roundBytes,
+        payer.publicKey.toBuffer(),
+      ],
+      PROGRAM_ID
+    );
+    console.log('Mint Record PDA:', mintRecordAccount.toBase58());
+
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    try {
+      // Старая запись должна быть 32-байтной
+      const oldRecordInfo = await connection.getAccountInfo(mintRecordAccount);
+      expect(oldRecordInfo).to.not.be.null;
+      expect(oldRecordInfo!.data.length).to.equal(32);
+      const oldMint = new PublicKey(oldRecordInfo!.data.slice(0, 32));
+      console.log('Адрес минта в старой записи:', oldMint.toBase58());
+
+      // Создаем буфер данных для инструкции
+      const instructionData = Buffer.alloc(11); // 1 байт для номера инструкции + 8 байт для раунда + 2 байта для NFTnumber
+      instructionData.writeUInt8(52, 0); // Номер инструкции
+      instructionData.writeBigUInt64LE(BigInt(ROUND_NUMBER), 1); // Номер раунда как u64
+      instructionData.writeUInt16LE(NFT_NUMBER, 9); // NFTnumber как u16
+      
+      console.log('Создаем инструкцию...');
+      const instruction = new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: mintRecordAccount, isSigner: false, isWritable: true },
+          { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          { pubkey: programConfig, isSigner: false, isWritable: false },
+          { pubkey: payer.publicKey, isSigner: false, isWritable: false }, // wallet
+        ],
+        data: instructionData
+      });
+
+      console.log('Создаем транзакцию...');
+      
+      // Увеличиваем лимит вычислительных единиц
+      const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
+        units: 400000
+      });
+      
+      const transaction = new Transaction()
+        .add(modifyComputeUnits)
+        .add(instruction);
+      
+      console.log('Отправляем транзакцию...');
+      const signature = await sendAndConfirmTransaction(
+        connection,
+        transaction,
+        [payer],
+        { skipPreflight: true, commitment: 'confirmed' }
+      );
+
+      console.log('Транзакция отправлена. Сигнатура:', signature);
+      
+      // Проверяем создание аккаунта
+      const mintRecordInfo = await connection.getAccountInfo(mintRecordAccount);
+      console.log('Mint Record аккаунт существует:', mintRecordInfo !== null);
+      if (mintRecordInfo) {
+        console.log('Размер Mint Record аккаунта:', mintRecordInfo.data.length);
+        console.log('Владелец Mint Record аккаунта:', mintRecordInfo.owner.toBase58());
+        
+        // Проверяем запись V2: key (1) + version (1) + round (8) + mint (32) + nft_number (2) + ...
+        expect(mintRecordInfo.data.length).to.equal(93);
+        expect(mintRecordInfo.data[1]).to.equal(2);
+        const storedMint = new PublicKey(mintRecordInfo.data.slice(10, 42));
+        console.log('Записанный адрес минта:', storedMint.toBase58());
+        expect(storedMint.equals(oldMint)).to.be.true;
+        expect(mintRecordInfo.data.readUInt16LE(42)).to.equal(NFT_NUMBER);
+      }
+
+      expect(mintRecordInfo).to.not.be.null;
+      
+      console.log('Тест успешно завершен! Запись о минте переведена в V2');
+
+    } catch (err: any) {
+      console.error('Произошла ошибка при выполнении теста:');
+      console.error('Тип ошибки:', err.constructor.name);
+      console.error('Сообщение ошибки:', err.message);
+      console.error('Полная ошибка:', err);
+      
+      // Получаем логи транзакции
+      if (err.signature) {
+        console.error('Получаем логи транзакции...');
+        try {
+          const txLogs = await connection.getTransaction(err.signature, { commitment: 'confirmed' });
+          console.error('Логи транзакции:');
+          console.error(JSON.stringify(txLogs, null, 2));
+        } catch (logErr) {
+          console.error('Не удалось получить логи транзакции:', logErr);
+        }
+      }
+      
+      throw err;
+    }
+  });
+}); 
\ No newline at end of file
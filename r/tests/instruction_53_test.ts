@@ -0,0 +1,145 @@
+/**
+ * Тест для инструкции 53: Создание битовой карты минтов раунда
+ *
+ * Емкость карты берется из b/rounds/N/d3.json: максимальный NFTnumber + 1.
+ * После создания карты инструкция 45 отмечает NFTnumber в ней вместо записи о минте.
+ * Карту можно создать только до начала раунда (start_timestamp в будущем).
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+// Укажите здесь номер раунда (1, 2, 3, ...)
+const ROUND_NUMBER = 3;
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Емкость карты по d3.json раунда: максимальный NFTnumber + 1
+function computeCapacity(roundNumber: number): number {
+  const roundDataPath = path.join(__dirname, `../../b/rounds/${roundNumber}/d3.json`);
+  if (!fs.existsSync(roundDataPath)) {
+    throw new Error(`Файл данных для раунда ${roundNumber} не найден: ${roundDataPath}`);
+  }
+  const d3Data = JSON.parse(fs.readFileSync(roundDataPath, 'utf8'));
+  return Math.max(...d3Data.map((item: { NFTnumber: number }) => item.NFTnumber)) + 1;
+}
+
+describe('Instruction 53', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should create claim bitmap for round', async function() {
+    console.log('Начинаем тест создания битовой карты минтов (инструкция 53)');
+
+    const roundNumber = process.env.TEST_ROUND_NUMBER ? parseInt(process.env.TEST_ROUND_NUMBER) : ROUND_NUMBER;
+    // Индекс раунда в программе - это (номер раунда - 1)
+    const roundIndex = roundNumber - 1;
+    console.log(`Раунд ${roundNumber}, индекс ${roundIndex}`);
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    console.log(`Адрес администратора: ${admin.publicKey.toBase58()}`);
+
+    const capacity = computeCapacity(roundNumber);
+    console.log('Емкость карты:', capacity);
+
+    const roundBuffer = Buffer.alloc(8);
+    roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+
+    const [claimBitmapAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from('claims'), roundBuffer],
+      PROGRAM_ID
+    );
+    console.log('Claim Bitmap PDA:', claimBitmapAccount.toBase58());
+
+    // Получаем PDA конфигурации программы (проверка администратора)
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    const [roundConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('round'), roundBuffer],
+      PROGRAM_ID
+    );
+    console.log('Round Config PDA:', roundConfig.toBase58());
+
+    const existing = await connection.getAccountInfo(claimBitmapAccount);
+    if (existing) {
+      console.log('Битовая карта для раунда уже существует');
+      this.skip();
+      return;
+    }
+
+    const capacityBuffer = Buffer.alloc(4);
+    capacityBuffer.writeUInt32LE(capacity, 0);
+
+    // [0] - номер инструкции, далее CreateClaimBitmapArgsV1 в формате Borsh
+    const dataBuffer = Buffer.concat([
+      Buffer.from([53]),
+      roundBuffer,
+      capacityBuffer,
+    ]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: claimBitmapAccount, isSigner: false, isWritable: true },
+        { pubkey: admin.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        { pubkey: roundConfig, isSigner: false, isWritable: false },
+      ],
+      data: dataBuffer,
+    });
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(instruction),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const claimBitmapInfo = await connection.getAccountInfo(claimBitmapAccount);
+    expect(claimBitmapInfo).to.not.be.null;
+    if (claimBitmapInfo) {
+      console.log('Размер Claim Bitmap аккаунта:', claimBitmapInfo.data.length);
+      expect(claimBitmapInfo.owner.equals(PROGRAM_ID)).to.be.true;
+      // key (1) + round (8) + capacity (4) + bump (1), затем биты
+      expect(claimBitmapInfo.data.length).to.equal(14 + Math.ceil(capacity / 8));
+      expect(claimBitmapInfo.data.readBigUInt64LE(1)).to.equal(BigInt(roundIndex));
+      expect(claimBitmapInfo.data.readUInt32LE(9)).to.equal(capacity);
+    }
+
+    console.log('Тест успешно завершен! Битовая карта минтов создана');
+  });
+});
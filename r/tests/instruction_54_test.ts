@@ -0,0 +1,166 @@
+/**
+ * Тест для инструкций 54 и 55: Пауза и снятие паузы
+ *
+ * Администратор ставит минт на глобальную паузу (54), проверяет флаг в конфигурации
+ * программы и снимает паузу (55). Пока пауза стоит, минт (инструкция 45) отклоняется;
+ * ставить паузу может только администратор.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Области паузы
+const PAUSE_MINT = 1;
+
+// Коды RError
+const UNAUTHORIZED = 20;
+const MINT_PAUSED = 35;
+
+// Ожидает отказ транзакции с кодом RError
+async function expectCustomError(promise: Promise<unknown>, code: number): Promise<void> {
+  try {
+    await promise;
+  } catch (err: any) {
+    const logs: string[] = err.logs || [];
+    expect(`${err.message}\n${logs.join('\n')}`).to.include(`custom program error: 0x${code.toString(16)}`);
+    return;
+  }
+  expect.fail(`Транзакция должна была завершиться ошибкой ${code}`);
+}
+
+// [номер инструкции, scope (u8), round (Option<u64>: 0 - глобальная пауза)]
+function pauseInstruction(instruction: number, programConfig: PublicKey, admin: PublicKey, scope: number): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: programConfig, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.from([instruction, scope, 0]),
+  });
+}
+
+// Флаги паузы идут после key (1), admin (32), pending_admin (Option) и bump (1)
+function readPausedFlags(data: Buffer): number {
+  const bumpOffset = data[33] === 0 ? 34 : 66;
+  return data[bumpOffset + 1];
+}
+
+// Минт по доказательству (инструкция 45): глобальная пауза проверяется сразу после загрузки
+// конфигурации программы (аккаунт 21), поэтому остальные аккаунты - заглушки, плательщик - аккаунт 4
+function mintWithProofInstruction(programConfig: PublicKey, payer: PublicKey): TransactionInstruction {
+  const keys = Array.from({ length: 23 }, () => ({ pubkey: SystemProgram.programId, isSigner: false, isWritable: false }));
+  keys[4] = { pubkey: payer, isSigner: true, isWritable: true };
+  keys[21] = { pubkey: programConfig, isSigner: false, isWritable: false };
+  // [45, round (u64), nft_number (u16), proof (пустой Vec)]
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys,
+    data: Buffer.concat([Buffer.from([45]), Buffer.alloc(8), Buffer.from([1, 0]), Buffer.alloc(4)]),
+  });
+}
+
+describe('Instructions 54 and 55', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should pause and unpause minting globally', async function() {
+    console.log('Начинаем тест паузы минта (инструкции 54 и 55)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    console.log('Администратор:', admin.publicKey.toBase58());
+
+    const [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from('config')],
+      PROGRAM_ID
+    );
+    console.log('Program Config PDA:', programConfig.toBase58());
+
+    // Шаг 1: ставим минт на паузу
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(pauseInstruction(54, programConfig, admin.publicKey, PAUSE_MINT)),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+
+    let configInfo = await connection.getAccountInfo(programConfig);
+    expect(configInfo).to.not.be.null;
+    expect(readPausedFlags(configInfo!.data) & PAUSE_MINT).to.equal(PAUSE_MINT);
+    console.log('Минт поставлен на паузу');
+
+    try {
+      // Шаг 2: минт на паузе отклоняется
+      await expectCustomError(
+        sendAndConfirmTransaction(
+          connection,
+          new Transaction().add(mintWithProofInstruction(programConfig, admin.publicKey)),
+          [admin],
+          { commitment: 'confirmed' }
+        ),
+        MINT_PAUSED
+      );
+      console.log('Минт на паузе отклонен');
+    } finally {
+      // Шаг 3: снимаем паузу
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(pauseInstruction(55, programConfig, admin.publicKey, PAUSE_MINT)),
+        [admin],
+        { commitment: 'confirmed' }
+      );
+    }
+
+    configInfo = await connection.getAccountInfo(programConfig);
+    expect(readPausedFlags(configInfo!.data) & PAUSE_MINT).to.equal(0);
+
+    console.log('Тест успешно завершен! Пауза минта снята');
+  });
+
+  it('should reject pause by a non-admin signer', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+
+    // Комиссию платит администратор, паузу подписывает посторонний кошелек
+    const intruder = Keypair.generate();
+    const transaction = new Transaction().add(pauseInstruction(54, programConfig, intruder.publicKey, PAUSE_MINT));
+    transaction.feePayer = admin.publicKey;
+    await expectCustomError(
+      sendAndConfirmTransaction(connection, transaction, [admin, intruder], { commitment: 'confirmed' }),
+      UNAUTHORIZED
+    );
+
+    const configInfo = await connection.getAccountInfo(programConfig);
+    expect(readPausedFlags(configInfo!.data) & PAUSE_MINT).to.equal(0);
+    console.log('Пауза от постороннего кошелька отклонена');
+  });
+});
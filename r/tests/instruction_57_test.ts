@@ -0,0 +1,152 @@
+/**
+ * Тест для инструкций 57 и 58: Распределение выручки между кошельками команды
+ *
+ * Администратор задает распределение (57): 60% себе и 40% второму кошельку
+ * (TEST_SPLIT_RECIPIENT или новый адрес). Казна пополняется и распределяется (58),
+ * проверяются выплаты, остаток от округления и счетчики в SplitConfig.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  clusterApiUrl,
+  LAMPORTS_PER_SOL
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// [57, recipients: Vec<SplitRecipient { address, basis_points (u16) }>]
+function setSplitConfigData(recipients: { address: PublicKey, basisPoints: number }[]): Buffer {
+  const data = Buffer.alloc(1 + 4 + recipients.length * 34);
+  data.writeUInt8(57, 0);
+  data.writeUInt32LE(recipients.length, 1);
+  recipients.forEach((recipient, i) => {
+    recipient.address.toBuffer().copy(data, 5 + i * 34);
+    data.writeUInt16LE(recipient.basisPoints, 5 + i * 34 + 32);
+  });
+  return data;
+}
+
+// SplitConfig: key (1), bump (1), total_distributed (u64), dust (u64), recipients
+function readSplitCounters(data: Buffer): { totalDistributed: bigint, dust: bigint } {
+  return {
+    totalDistributed: data.readBigUInt64LE(2),
+    dust: data.readBigUInt64LE(10),
+  };
+}
+
+describe('Instructions 57 and 58', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should set split config and distribute treasury balance', async function() {
+    console.log('Начинаем тест распределения выручки (инструкции 57 и 58)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    const secondRecipient = process.env.TEST_SPLIT_RECIPIENT
+      ? new PublicKey(process.env.TEST_SPLIT_RECIPIENT)
+      : Keypair.generate().publicKey;
+    console.log('Администратор:', admin.publicKey.toBase58());
+    console.log('Второй получатель:', secondRecipient.toBase58());
+
+    const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+    const [splitConfig] = PublicKey.findProgramAddressSync([Buffer.from('split')], PROGRAM_ID);
+    const [treasury] = PublicKey.findProgramAddressSync([Buffer.from('treasury')], PROGRAM_ID);
+    console.log('Split Config PDA:', splitConfig.toBase58());
+    console.log('Treasury PDA:', treasury.toBase58());
+
+    // Шаг 1: задаем распределение 60/40
+    const recipients = [
+      { address: admin.publicKey, basisPoints: 6000 },
+      { address: secondRecipient, basisPoints: 4000 },
+    ];
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: splitConfig, isSigner: false, isWritable: true },
+          { pubkey: admin.publicKey, isSigner: true, isWritable: true },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          { pubkey: programConfig, isSigner: false, isWritable: false },
+        ],
+        data: setSplitConfigData(recipients),
+      })),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+
+    const countersBefore = readSplitCounters((await connection.getAccountInfo(splitConfig))!.data);
+    console.log('Выплачено до распределения:', countersBefore.totalDistributed.toString());
+
+    // Шаг 2: пополняем казну, чтобы второму получателю хватило на ренту
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(SystemProgram.transfer({
+        fromPubkey: admin.publicKey,
+        toPubkey: treasury,
+        lamports: 0.01 * LAMPORTS_PER_SOL,
+      })),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+
+    const rentExemptMinimum = await connection.getMinimumBalanceForRentExemption(0);
+    const distributable = (await connection.getBalance(treasury)) - rentExemptMinimum;
+    const secondBalanceBefore = await connection.getBalance(secondRecipient);
+    console.log('К распределению:', distributable, 'lamports');
+
+    // Шаг 3: распределяем (подпись администратора не нужна, admin только платит комиссию)
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(new TransactionInstruction({
+        programId: PROGRAM_ID,
+        keys: [
+          { pubkey: splitConfig, isSigner: false, isWritable: true },
+          { pubkey: treasury, isSigner: false, isWritable: true },
+          { pubkey: programConfig, isSigner: false, isWritable: false },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          ...recipients.map(recipient => ({ pubkey: recipient.address, isSigner: false, isWritable: true })),
+        ],
+        data: Buffer.from([58]),
+      })),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+
+    // Доли округляются вниз, остаток остается в казне
+    const firstShare = Math.floor(distributable * 6000 / 10000);
+    const secondShare = Math.floor(distributable * 4000 / 10000);
+    const dust = distributable - firstShare - secondShare;
+
+    const secondBalanceAfter = await connection.getBalance(secondRecipient);
+    expect(secondBalanceAfter - secondBalanceBefore).to.equal(secondShare);
+    expect(await connection.getBalance(treasury)).to.equal(rentExemptMinimum + dust);
+
+    const countersAfter = readSplitCounters((await connection.getAccountInfo(splitConfig))!.data);
+    expect(countersAfter.totalDistributed - countersBefore.totalDistributed).to.equal(BigInt(firstShare + secondShare));
+    // SplitConfig.dust - остаток последнего распределения, а не сумма остатков
+    expect(countersAfter.dust).to.equal(BigInt(dust));
+
+    console.log('Тест успешно завершен! Выручка распределена, остаток:', dust);
+  });
+});
@@ -0,0 +1,157 @@
+/**
+ * Тест для инструкции 59: Пакетное обновление метаданных NFT
+ *
+ * Обновляет URI у минтов из TEST_BATCH_MINTS (через запятую, без него тест
+ * пропускается) и добавляет в пакет случайный минт без метаданных. Статусы из
+ * return data: 0 - обновлено, 1 - ошибка проверки, 2 - пропущено из-за
+ * вычислительного бюджета.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  clusterApiUrl
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import bs58 from 'bs58';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+// Минты NFT через запятую; без TEST_BATCH_MINTS тест пропускается
+const BATCH_MINTS = (process.env.TEST_BATCH_MINTS || '')
+  .split(',')
+  .map(mint => mint.trim())
+  .filter(mint => mint.length > 0)
+  .map(mint => new PublicKey(mint));
+
+// URI получает номер позиции в пакете: {base}/{i}.json
+const URI_BASE = process.env.TEST_URI_BASE || 'https://a.b';
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Borsh-строка: u32 LE длина + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
+
+// UpdateMetadataArgsV1 только с URI: остальные поля None
+function uriUpdate(mint: PublicKey, uri: string): Buffer {
+  return Buffer.concat([
+    mint.toBuffer(),
+    Buffer.from([0, 0, 0]), // creators, name, symbol
+    Buffer.from([1]), borshString(uri),
+    Buffer.from([0, 0, 0]), // sellerFeeBasisPoints, primarySaleHappened, isMutable
+  ]);
+}
+
+function findMetadata(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+describe('Instruction 59', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should update metadata of several mints and report statuses', async function() {
+    console.log('Начинаем тест пакетного обновления метаданных (инструкция 59)');
+
+    if (BATCH_MINTS.length === 0) {
+      console.log('TEST_BATCH_MINTS не задан: минты NFT через запятую, тест пропущен');
+      this.skip();
+    }
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const [programAuthority] = PublicKey.findProgramAddressSync([Buffer.from('mint_authority')], PROGRAM_ID);
+    const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+
+    // Последний элемент - минт без метаданных, его обновление должно завершиться ошибкой
+    const mints = [...BATCH_MINTS, Keypair.generate().publicKey];
+    const updates = mints.map((mint, i) => uriUpdate(mint, `${URI_BASE}/${i}.json`));
+
+    const countBuffer = Buffer.alloc(4);
+    countBuffer.writeUInt32LE(updates.length, 0);
+    const data = Buffer.concat([Buffer.from([59]), countBuffer, ...updates]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: programAuthority, isSigner: false, isWritable: false },
+        { pubkey: admin.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        ...mints.flatMap(mint => [
+          { pubkey: findMetadata(mint), isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ]),
+      ],
+      data,
+    });
+
+    const transaction = new Transaction()
+      .add(ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }))
+      .add(instruction);
+
+    // Статусы читаем из return data симуляции
+    transaction.feePayer = admin.publicKey;
+    const simulation = await connection.simulateTransaction(transaction, [admin]);
+    if (simulation.value.err) {
+      console.error('Логи симуляции:', simulation.value.logs);
+      throw new Error(`Симуляция завершилась ошибкой: ${JSON.stringify(simulation.value.err)}`);
+    }
+    const returnData = Buffer.from(simulation.value.returnData!.data[0], 'base64');
+    // Vec<BatchUpdateStatus>: u32 LE длина + по байту на обновление
+    const statuses = [...returnData.subarray(4, 4 + returnData.readUInt32LE(0))];
+    console.log('Статусы обновлений:', statuses);
+
+    expect(statuses.length).to.equal(mints.length);
+    expect(statuses[statuses.length - 1]).to.equal(1);
+    statuses.slice(0, -1).forEach(status => expect(status).to.be.oneOf([0, 2]));
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      transaction,
+      [admin],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    // URI обновленных NFT содержит новый адрес
+    for (let i = 0; i < BATCH_MINTS.length; i++) {
+      if (statuses[i] !== 0) {
+        continue;
+      }
+      const metadataAccount = await connection.getAccountInfo(findMetadata(BATCH_MINTS[i]));
+      expect(metadataAccount!.data.toString('utf8')).to.include(`${URI_BASE}/${i}.json`);
+    }
+
+    console.log('Тест успешно завершен! Обновлено:', statuses.filter(status => status === 0).length);
+  });
+});
@@ -0,0 +1,263 @@
+/**
+ * Тест для инструкции 60: Отложенное раскрытие метаданных NFT
+ *
+ * Строит дерево по манифесту TEST_REVEAL_MANIFEST ({ "NFTnumber": "uri" }), тем же,
+ * что зафиксирован инструкцией 47, и раскрывает NFT TEST_REVEAL_MINT с номером
+ * TEST_REVEAL_NFT_NUMBER. Транзакцию оплачивает новый кошелек: подпись
+ * администратора не нужна. URI вне манифеста, чужая программа Token Metadata и
+ * доказательство другого NFTnumber для этого NFT отклоняются.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  clusterApiUrl,
+  LAMPORTS_PER_SOL
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import { createHash } from 'crypto';
+import bs58 from 'bs58';
+import { MerkleTree } from 'merkletreejs';
+import * as fs from 'fs';
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+if (!process.env.TEST_REVEAL_MANIFEST || !process.env.TEST_REVEAL_MINT || !process.env.TEST_REVEAL_NFT_NUMBER) {
+  throw new Error('Задайте TEST_REVEAL_MANIFEST, TEST_REVEAL_MINT и TEST_REVEAL_NFT_NUMBER');
+}
+const ROUND_NUMBER = parseInt(process.env.TEST_ROUND || '3');
+const REVEAL_MINT = new PublicKey(process.env.TEST_REVEAL_MINT);
+const NFT_NUMBER = parseInt(process.env.TEST_REVEAL_NFT_NUMBER);
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Функция для вычисления sha256 хеша
+function sha256(data: Buffer): Buffer {
+  return createHash('sha256').update(data).digest();
+}
+
+// Borsh-строка: u32 LE длина + байты UTF-8
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length, 0);
+  return Buffer.concat([len, bytes]);
+}
+
+// Лист манифеста: sha256(NFTnumber LE || uri)
+function revealLeaf(nftNumber: number, uri: string): Buffer {
+  const nftNumberBuffer = Buffer.alloc(2);
+  nftNumberBuffer.writeUInt16LE(nftNumber, 0);
+  return sha256(Buffer.concat([nftNumberBuffer, Buffer.from(uri, 'utf8')]));
+}
+
+// Коды RError
+const INVALID_REVEAL_PROOF = 57;
+const REVEAL_MINT_MISMATCH = 58;
+const INVALID_TOKEN_METADATA_PROGRAM = 68;
+
+// Ожидает отказ транзакции с кодом RError
+async function expectCustomError(promise: Promise<unknown>, code: number): Promise<void> {
+  try {
+    await promise;
+  } catch (err: any) {
+    const logs: string[] = err.logs || [];
+    expect(`${err.message}\n${logs.join('\n')}`).to.include(`custom program error: 0x${code.toString(16)}`);
+    return;
+  }
+  expect.fail(`Транзакция должна была завершиться ошибкой ${code}`);
+}
+
+// Манифест TEST_REVEAL_MANIFEST и дерево по нему
+function loadManifest() {
+  const manifest: Record<string, string> = JSON.parse(fs.readFileSync(process.env.TEST_REVEAL_MANIFEST!, 'utf8'));
+  const leaves = Object.entries(manifest).map(([nftNumber, value]) => revealLeaf(parseInt(nftNumber), value));
+  const tree = new MerkleTree(leaves.slice().sort(Buffer.compare), sha256, { sortPairs: true });
+  const proofFor = (nftNumber: number, uri: string) => tree.getProof(revealLeaf(nftNumber, uri)).map(node => node.data);
+  return { manifest, proofFor };
+}
+
+// [60, round (u64), nft_number (u16), uri (String), proof (Vec<[u8; 32]>)] для NFT REVEAL_MINT
+function revealInstruction(
+  payer: PublicKey,
+  nftNumber: number,
+  uri: string,
+  proof: Buffer[],
+  tokenMetadataProgram: PublicKey = TOKEN_METADATA_PROGRAM_ID
+): TransactionInstruction {
+  const roundBuffer = Buffer.alloc(8);
+  roundBuffer.writeBigUInt64LE(BigInt(ROUND_NUMBER - 1), 0);
+  const [roundConfig] = PublicKey.findProgramAddressSync([Buffer.from('round'), roundBuffer], PROGRAM_ID);
+  const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [programAuthority] = PublicKey.findProgramAddressSync([Buffer.from('mint_authority')], PROGRAM_ID);
+
+  const nftNumberBuffer = Buffer.alloc(2);
+  nftNumberBuffer.writeUInt16LE(nftNumber, 0);
+  const proofLength = Buffer.alloc(4);
+  proofLength.writeUInt32LE(proof.length, 0);
+  const data = Buffer.concat([
+    Buffer.from([60]),
+    roundBuffer,
+    nftNumberBuffer,
+    borshString(uri),
+    proofLength,
+    ...proof,
+  ]);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findMetadata(REVEAL_MINT), isSigner: false, isWritable: true },
+      { pubkey: REVEAL_MINT, isSigner: false, isWritable: false },
+      { pubkey: programAuthority, isSigner: false, isWritable: false },
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: tokenMetadataProgram, isSigner: false, isWritable: false },
+      { pubkey: roundConfig, isSigner: false, isWritable: false },
+      { pubkey: programConfig, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
+
+function findMetadata(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+async function sendReveal(connection: Connection, instruction: TransactionInstruction, payer: Keypair): Promise<string> {
+  return sendAndConfirmTransaction(
+    connection,
+    new Transaction()
+      .add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }))
+      .add(instruction),
+    [payer],
+    { commitment: 'confirmed' }
+  );
+}
+
+describe('Instruction 60', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should reveal NFT URI with a proof against the round commitment', async function() {
+    console.log('Начинаем тест раскрытия (инструкция 60)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { manifest, proofFor } = loadManifest();
+    const uri = manifest[NFT_NUMBER.toString()];
+    if (!uri) {
+      throw new Error(`NFTnumber ${NFT_NUMBER} отсутствует в манифесте`);
+    }
+    const proof = proofFor(NFT_NUMBER, uri);
+    console.log('URI:', uri);
+    console.log('Длина доказательства:', proof.length);
+
+    // Раскрывать может любой: платит новый кошелек, пополненный администратором
+    const revealer = Keypair.generate();
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(SystemProgram.transfer({
+        fromPubkey: admin.publicKey,
+        toPubkey: revealer.publicKey,
+        lamports: 0.01 * LAMPORTS_PER_SOL,
+      })),
+      [admin],
+      { commitment: 'confirmed' }
+    );
+
+    const signature = await sendReveal(connection, revealInstruction(revealer.publicKey, NFT_NUMBER, uri, proof), revealer);
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const metadataAccount = await connection.getAccountInfo(findMetadata(REVEAL_MINT));
+    expect(metadataAccount!.data.toString('utf8')).to.include(uri);
+
+    console.log('Тест успешно завершен! NFT раскрыт');
+  });
+
+  it('should reject a URI outside the manifest', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { manifest, proofFor } = loadManifest();
+    const uri = manifest[NFT_NUMBER.toString()];
+    const proof = proofFor(NFT_NUMBER, uri);
+
+    await expectCustomError(
+      sendReveal(connection, revealInstruction(admin.publicKey, NFT_NUMBER, `${uri}#rare`, proof), admin),
+      INVALID_REVEAL_PROOF
+    );
+    console.log('URI вне манифеста отклонен');
+  });
+
+  it('should reject a substituted Token Metadata program', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    const { manifest, proofFor } = loadManifest();
+    const uri = manifest[NFT_NUMBER.toString()];
+    const proof = proofFor(NFT_NUMBER, uri);
+
+    await expectCustomError(
+      sendReveal(connection, revealInstruction(admin.publicKey, NFT_NUMBER, uri, proof, SystemProgram.programId), admin),
+      INVALID_TOKEN_METADATA_PROGRAM
+    );
+    console.log('Чужая программа Token Metadata отклонена');
+  });
+
+  it('should reject a proof of another NFTnumber for this NFT', async function() {
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const admin = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+
+    // Доказательство верное, но название NFT содержит другой NFTnumber
+    const { manifest, proofFor } = loadManifest();
+    const other = Object.keys(manifest).map(nftNumber => parseInt(nftNumber)).find(nftNumber => nftNumber !== NFT_NUMBER);
+    if (other === undefined) {
+      console.log('В манифесте один NFTnumber, пропускаем тест');
+      this.skip();
+      return;
+    }
+    const uri = manifest[other.toString()];
+
+    await expectCustomError(
+      sendReveal(connection, revealInstruction(admin.publicKey, other, uri, proofFor(other, uri)), admin),
+      REVEAL_MINT_MISMATCH
+    );
+    console.log(`Доказательство NFTnumber ${other} для NFT ${NFT_NUMBER} отклонено`);
+  });
+});
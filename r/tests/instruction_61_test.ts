@@ -0,0 +1,239 @@
+/**
+ * Тест для инструкции 61: Минт нескольких pNFT по мультидоказательству Merkle
+ *
+ * Находит в b/rounds/N/d3.json все NFTnumber кошелька PRIVATE_KEY (повторяющийся
+ * player), строит дерево раунда как инструкция 47 (TEST_LEAF_V2=1 - формат V2) и
+ * минтит первые TEST_MULTI_MINT_COUNT из них (по умолчанию 2) одной транзакцией.
+ * Для раунда должна быть создана битовая карта минтов (инструкция 53).
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  clusterApiUrl
+} from '@solana/web3.js';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync
+} from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import { createHash } from 'crypto';
+import bs58 from 'bs58';
+import { MerkleTree } from 'merkletreejs';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+const ROUND_NUMBER = parseInt(process.env.TEST_ROUND_NUMBER || '3');
+const MULTI_MINT_COUNT = parseInt(process.env.TEST_MULTI_MINT_COUNT || '2');
+const LEAF_VERSION = process.env.TEST_LEAF_V2 === '1' ? 1 : 0;
+const NFT_TOKEN_PROGRAM_ID = process.env.TEST_TOKEN_2022 === '1' ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Функция для вычисления sha256 хеша
+function sha256(data: Buffer): Buffer {
+  return createHash('sha256').update(data).digest();
+}
+
+// Лист раунда: V1 - sha256(pubkey || NFTnumber LE), V2 - sha256(0x00 || program_id || round LE || pubkey || NFTnumber LE)
+function roundLeaf(player: PublicKey, nftNumber: number, roundIndex: number): Buffer {
+  const nftNumberBuffer = Buffer.alloc(2);
+  nftNumberBuffer.writeUInt16LE(nftNumber, 0);
+  const walletLeaf = Buffer.concat([player.toBuffer(), nftNumberBuffer]);
+  if (LEAF_VERSION === 0) {
+    return sha256(walletLeaf);
+  }
+  const roundBuffer = Buffer.alloc(8);
+  roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+  return sha256(Buffer.concat([Buffer.from([0]), PROGRAM_ID.toBuffer(), roundBuffer, walletLeaf]));
+}
+
+// Узел: V1 - sha256(min || max), V2 - sha256(0x01 || min || max)
+function hashNode(data: Buffer): Buffer {
+  return LEAF_VERSION === 0 ? sha256(data) : sha256(Buffer.concat([Buffer.from([1]), data]));
+}
+
+// Мультидоказательство как в r::merkle::verify_merkle_multiproof: индексы листьев
+// и недостающие соседи по уровням снизу вверх, слева направо
+function getMultiproof(layers: Buffer[][], leaves: Buffer[]): { indices: number[], proof: Buffer[] } {
+  const indices = leaves.map(leaf => layers[0].findIndex(node => node.equals(leaf)));
+  if (indices.includes(-1)) {
+    throw new Error('Лист не найден в дереве раунда');
+  }
+  let known = [...new Set(indices)].sort((a, b) => a - b);
+  const proof: Buffer[] = [];
+  for (const layer of layers.slice(0, -1)) {
+    known.forEach(index => {
+      const sibling = index ^ 1;
+      if (!known.includes(sibling) && sibling < layer.length) {
+        proof.push(layer[sibling]);
+      }
+    });
+    known = [...new Set(known.map(index => Math.floor(index / 2)))];
+  }
+  return { indices, proof };
+}
+
+function findMetadataPda(mint: PublicKey, suffix: Buffer[] = []): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), ...suffix],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+describe('Instruction 61', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should mint several pNFTs of one wallet with a single multiproof', async function() {
+    console.log('Начинаем тест минта по мультидоказательству (инструкция 61)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const payer = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    const roundIndex = ROUND_NUMBER - 1;
+
+    const roundDataPath = path.join(__dirname, `../../b/rounds/${ROUND_NUMBER}/d3.json`);
+    const d3Data: { player: string, NFTnumber: number }[] = JSON.parse(fs.readFileSync(roundDataPath, 'utf8'));
+
+    const nftNumbers = d3Data
+      .filter(item => item.player === payer.publicKey.toBase58())
+      .map(item => item.NFTnumber)
+      .slice(0, MULTI_MINT_COUNT);
+    if (nftNumbers.length < 2) {
+      console.log(`У ${payer.publicKey.toBase58()} меньше двух NFTnumber в раунде ${ROUND_NUMBER}, тест пропущен`);
+      this.skip();
+    }
+    console.log('NFTnumber кошелька:', nftNumbers);
+
+    const leaves = d3Data.map(item => roundLeaf(new PublicKey(item.player), item.NFTnumber, roundIndex));
+    const tree = new MerkleTree(leaves.slice().sort(Buffer.compare), hashNode, { sortPairs: true });
+    const { indices, proof } = getMultiproof(
+      tree.getLayers(),
+      nftNumbers.map(nftNumber => roundLeaf(payer.publicKey, nftNumber, roundIndex))
+    );
+    console.log('Индексы листьев:', indices, 'узлов в доказательстве:', proof.length);
+
+    const roundBuffer = Buffer.alloc(8);
+    roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+    const [programAuthority] = PublicKey.findProgramAddressSync([Buffer.from('mint_authority')], PROGRAM_ID);
+    const [roundConfig] = PublicKey.findProgramAddressSync([Buffer.from('round'), roundBuffer], PROGRAM_ID);
+    const [claimBitmap] = PublicKey.findProgramAddressSync([Buffer.from('claims'), roundBuffer], PROGRAM_ID);
+    const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+    const [treasury] = PublicKey.findProgramAddressSync([Buffer.from('treasury')], PROGRAM_ID);
+
+    // Коллекция раунда (RoundConfig, байты 41..73); тест ожидает, что она задана у раунда
+    const roundConfigInfo = await connection.getAccountInfo(roundConfig);
+    if (!roundConfigInfo) {
+      throw new Error('Конфигурация раунда не создана (инструкция 47)');
+    }
+    const collectionMint = new PublicKey(roundConfigInfo.data.subarray(41, 73));
+    expect(collectionMint.equals(PublicKey.default)).to.equal(false);
+
+    // Аккаунты каждого NFT: metadata, master edition, mint, token account, token record
+    const mints = nftNumbers.map(() => Keypair.generate());
+    const nftKeys = mints.flatMap(mint => {
+      const tokenAccount = getAssociatedTokenAddressSync(mint.publicKey, payer.publicKey, false, NFT_TOKEN_PROGRAM_ID);
+      return [
+        { pubkey: findMetadataPda(mint.publicKey), isSigner: false, isWritable: true },
+        { pubkey: findMetadataPda(mint.publicKey, [Buffer.from('edition')]), isSigner: false, isWritable: true },
+        { pubkey: mint.publicKey, isSigner: true, isWritable: true },
+        { pubkey: tokenAccount, isSigner: false, isWritable: true },
+        {
+          pubkey: findMetadataPda(mint.publicKey, [Buffer.from('token_record'), tokenAccount.toBuffer()]),
+          isSigner: false,
+          isWritable: true,
+        },
+      ];
+    });
+
+    // [61, round (u64), nft_numbers (Vec<u16>), leaf_indices (Vec<u32>), leaf_count (u32), proof (Vec<[u8; 32]>)]
+    const nftNumbersBuffer = Buffer.alloc(4 + nftNumbers.length * 2);
+    nftNumbersBuffer.writeUInt32LE(nftNumbers.length, 0);
+    nftNumbers.forEach((nftNumber, i) => nftNumbersBuffer.writeUInt16LE(nftNumber, 4 + i * 2));
+    const indicesBuffer = Buffer.alloc(4 + indices.length * 4);
+    indicesBuffer.writeUInt32LE(indices.length, 0);
+    indices.forEach((index, i) => indicesBuffer.writeUInt32LE(index, 4 + i * 4));
+    const leafCountBuffer = Buffer.alloc(4);
+    leafCountBuffer.writeUInt32LE(leaves.length, 0);
+    const proofLength = Buffer.alloc(4);
+    proofLength.writeUInt32LE(proof.length, 0);
+    const data = Buffer.concat([
+      Buffer.from([61]),
+      roundBuffer,
+      nftNumbersBuffer,
+      indicesBuffer,
+      leafCountBuffer,
+      proofLength,
+      ...proof,
+    ]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: programAuthority, isSigner: false, isWritable: true },
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: NFT_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: collectionMint, isSigner: false, isWritable: false },
+        { pubkey: findMetadataPda(collectionMint), isSigner: false, isWritable: true },
+        { pubkey: findMetadataPda(collectionMint, [Buffer.from('edition')]), isSigner: false, isWritable: false },
+        { pubkey: roundConfig, isSigner: false, isWritable: false },
+        { pubkey: claimBitmap, isSigner: false, isWritable: true },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        { pubkey: treasury, isSigner: false, isWritable: true },
+        ...nftKeys,
+      ],
+      data,
+    });
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction()
+        .add(ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }))
+        .add(instruction),
+      [payer, ...mints],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    // Каждому NFTnumber соответствует новый токен на кошельке
+    for (const mint of mints) {
+      const tokenAccount = getAssociatedTokenAddressSync(mint.publicKey, payer.publicKey, false, NFT_TOKEN_PROGRAM_ID);
+      const balance = await connection.getTokenAccountBalance(tokenAccount);
+      expect(balance.value.amount).to.equal('1');
+    }
+
+    console.log('Тест успешно завершен! Заминчено NFT:', mints.length);
+  });
+});
@@ -0,0 +1,220 @@
+/**
+ * Тест для инструкции 62: Минт pNFT в пределах лимита кошелька
+ *
+ * Раунд создается инструкцией 47 с TEST_ALLOWANCE=1: лист на кошелек со всеми его
+ * NFTnumber из b/rounds/N/d3.json. Минтит следующие TEST_ALLOWANCE_QUANTITY
+ * (по умолчанию 1) NFT кошелька PRIVATE_KEY и проверяет счетчик в записи лимита.
+ * Повторные запуски минтят следующие номера, пока лимит не исчерпан.
+ */
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+  ComputeBudgetProgram,
+  clusterApiUrl
+} from '@solana/web3.js';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync
+} from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import * as dotenv from 'dotenv';
+import { createHash } from 'crypto';
+import bs58 from 'bs58';
+import { MerkleTree } from 'merkletreejs';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+dotenv.config();
+
+// Получаем ID программы из переменной окружения
+if (!process.env.PROGRAM_ID) {
+  throw new Error('Переменная окружения PROGRAM_ID не задана. Пожалуйста, установите её перед запуском теста.');
+}
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID);
+
+const ROUND_NUMBER = parseInt(process.env.TEST_ROUND_NUMBER || '3');
+const QUANTITY = parseInt(process.env.TEST_ALLOWANCE_QUANTITY || '1');
+const NFT_TOKEN_PROGRAM_ID = process.env.TEST_TOKEN_2022 === '1' ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Функция для вычисления sha256 хеша
+function sha256(data: Buffer): Buffer {
+  return createHash('sha256').update(data).digest();
+}
+
+// Номера NFT в листе: количество u16 LE, затем NFTnumber u16 LE каждого
+function encodeNftNumbers(nftNumbers: number[]): Buffer {
+  const buffer = Buffer.alloc(2 + nftNumbers.length * 2);
+  buffer.writeUInt16LE(nftNumbers.length, 0);
+  nftNumbers.forEach((nftNumber, i) => buffer.writeUInt16LE(nftNumber, 2 + i * 2));
+  return buffer;
+}
+
+// Лист кошелька: sha256(0x00 || program_id || round LE || pubkey || count u16 LE || NFTnumber LE * count)
+function allowanceLeaf(player: PublicKey, nftNumbers: number[], roundBuffer: Buffer): Buffer {
+  return sha256(Buffer.concat([
+    Buffer.from([0]), PROGRAM_ID.toBuffer(), roundBuffer, player.toBuffer(), encodeNftNumbers(nftNumbers),
+  ]));
+}
+
+// Узел: sha256(0x01 || min || max)
+function hashNode(data: Buffer): Buffer {
+  return sha256(Buffer.concat([Buffer.from([1]), data]));
+}
+
+function findMetadataPda(mint: PublicKey, suffix: Buffer[] = []): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), ...suffix],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+describe('Instruction 62', function() {
+  // Увеличиваем таймаут до 60 секунд
+  this.timeout(60000);
+
+  it('should mint the next NFTs within the wallet allowance', async function() {
+    console.log('Начинаем тест минта по лимиту кошелька (инструкция 62)');
+
+    if (!process.env.PRIVATE_KEY) {
+      throw new Error('PRIVATE_KEY не найден в переменных окружения');
+    }
+
+    const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
+    const payer = Keypair.fromSecretKey(bs58.decode(process.env.PRIVATE_KEY));
+    const roundIndex = ROUND_NUMBER - 1;
+    const roundBuffer = Buffer.alloc(8);
+    roundBuffer.writeBigUInt64LE(BigInt(roundIndex), 0);
+
+    // Все NFTnumber каждого кошелька в порядке d3.json
+    const roundDataPath = path.join(__dirname, `../../b/rounds/${ROUND_NUMBER}/d3.json`);
+    const d3Data: { player: string, NFTnumber: number }[] = JSON.parse(fs.readFileSync(roundDataPath, 'utf8'));
+    const allowances = new Map<string, number[]>();
+    d3Data.forEach(item => allowances.set(item.player, [...(allowances.get(item.player) || []), item.NFTnumber]));
+
+    const nftNumbers = allowances.get(payer.publicKey.toBase58());
+    if (!nftNumbers) {
+      throw new Error(`Кошелек ${payer.publicKey.toBase58()} не найден в раунде ${ROUND_NUMBER}`);
+    }
+    console.log('NFTnumber кошелька (лимит):', nftNumbers);
+
+    const leaves = [...allowances.entries()].map(([player, numbers]) => allowanceLeaf(new PublicKey(player), numbers, roundBuffer));
+    const tree = new MerkleTree(leaves.sort(Buffer.compare), hashNode, { sortPairs: true });
+    const leaf = allowanceLeaf(payer.publicKey, nftNumbers, roundBuffer);
+    const proof = tree.getProof(leaf).map(node => node.data);
+    expect(tree.verify(proof, leaf, tree.getRoot())).to.equal(true);
+
+    const [programAuthority] = PublicKey.findProgramAddressSync([Buffer.from('mint_authority')], PROGRAM_ID);
+    const [roundConfig] = PublicKey.findProgramAddressSync([Buffer.from('round'), roundBuffer], PROGRAM_ID);
+    const [allowanceRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from('allowance'), roundBuffer, payer.publicKey.toBuffer()],
+      PROGRAM_ID
+    );
+    const [programConfig] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+    const [treasury] = PublicKey.findProgramAddressSync([Buffer.from('treasury')], PROGRAM_ID);
+
+    // Счетчик минтов: key (1) + round (8) + wallet (32) + minted (u16)
+    const recordBefore = await connection.getAccountInfo(allowanceRecord);
+    const mintedBefore = recordBefore ? recordBefore.data.readUInt16LE(41) : 0;
+    console.log(`Заминчено ранее: ${mintedBefore} из ${nftNumbers.length}`);
+    if (mintedBefore + QUANTITY > nftNumbers.length) {
+      console.log('Лимит кошелька исчерпан, тест пропущен');
+      this.skip();
+    }
+
+    // Коллекция раунда (RoundConfig, байты 41..73); тест ожидает, что она задана у раунда
+    const roundConfigInfo = await connection.getAccountInfo(roundConfig);
+    if (!roundConfigInfo) {
+      throw new Error('Конфигурация раунда не создана (инструкция 47)');
+    }
+    const collectionMint = new PublicKey(roundConfigInfo.data.subarray(41, 73));
+    expect(collectionMint.equals(PublicKey.default)).to.equal(false);
+
+    // Аккаунты каждого NFT: metadata, master edition, mint, token account, token record
+    const mints = Array.from({ length: QUANTITY }, () => Keypair.generate());
+    const nftKeys = mints.flatMap(mint => {
+      const tokenAccount = getAssociatedTokenAddressSync(mint.publicKey, payer.publicKey, false, NFT_TOKEN_PROGRAM_ID);
+      return [
+        { pubkey: findMetadataPda(mint.publicKey), isSigner: false, isWritable: true },
+        { pubkey: findMetadataPda(mint.publicKey, [Buffer.from('edition')]), isSigner: false, isWritable: true },
+        { pubkey: mint.publicKey, isSigner: true, isWritable: true },
+        { pubkey: tokenAccount, isSigner: false, isWritable: true },
+        {
+          pubkey: findMetadataPda(mint.publicKey, [Buffer.from('token_record'), tokenAccount.toBuffer()]),
+          isSigner: false,
+          isWritable: true,
+        },
+      ];
+    });
+
+    // [62, round (u64), nft_numbers (Vec<u16>), quantity (u16), proof (Vec<[u8; 32]>)]
+    const nftNumbersBuffer = Buffer.alloc(4 + nftNumbers.length * 2);
+    nftNumbersBuffer.writeUInt32LE(nftNumbers.length, 0);
+    nftNumbers.forEach((nftNumber, i) => nftNumbersBuffer.writeUInt16LE(nftNumber, 4 + i * 2));
+    const quantityBuffer = Buffer.alloc(2);
+    quantityBuffer.writeUInt16LE(QUANTITY, 0);
+    const proofLength = Buffer.alloc(4);
+    proofLength.writeUInt32LE(proof.length, 0);
+    const data = Buffer.concat([
+      Buffer.from([62]),
+      roundBuffer,
+      nftNumbersBuffer,
+      quantityBuffer,
+      proofLength,
+      ...proof,
+    ]);
+
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: programAuthority, isSigner: false, isWritable: true },
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: NFT_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: collectionMint, isSigner: false, isWritable: false },
+        { pubkey: findMetadataPda(collectionMint), isSigner: false, isWritable: true },
+        { pubkey: findMetadataPda(collectionMint, [Buffer.from('edition')]), isSigner: false, isWritable: false },
+        { pubkey: roundConfig, isSigner: false, isWritable: false },
+        { pubkey: allowanceRecord, isSigner: false, isWritable: true },
+        { pubkey: programConfig, isSigner: false, isWritable: false },
+        { pubkey: treasury, isSigner: false, isWritable: true },
+        ...nftKeys,
+      ],
+      data,
+    });
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction()
+        .add(ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }))
+        .add(instruction),
+      [payer, ...mints],
+      { commitment: 'confirmed' }
+    );
+    console.log('Транзакция отправлена. Сигнатура:', signature);
+
+    const recordAfter = await connection.getAccountInfo(allowanceRecord);
+    expect(recordAfter!.data.readUInt16LE(41)).to.equal(mintedBefore + QUANTITY);
+
+    console.log(`Тест успешно завершен! Заминчено ${mintedBefore + QUANTITY} из ${nftNumbers.length}`);
+  });
+});